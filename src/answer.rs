@@ -0,0 +1,223 @@
+use std::fmt::{self, Display};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The answer to a single part of a puzzle.
+///
+/// Integers compare by value regardless of their width, so an `I64(5)` equals a `U128(5)`. Text
+/// compares to an integer as it is printed, so `"5"` equals `5`, but `"007"` does not equal `7`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Text(String),
+    /// Multi-line output, such as letters drawn on a screen.
+    Rendered(String),
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Answer::I64(n) => Some(n.into()),
+            Answer::U64(n) => Some(n.into()),
+            Answer::I128(n) => Some(n),
+            Answer::U128(n) => n.try_into().ok(),
+            Answer::Text(_) | Answer::Rendered(_) => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Answer::Text(s) | Answer::Rendered(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.as_str().is_some_and(|s| s.contains('\n'))
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::U128(a), Answer::U128(b)) => a == b,
+            _ => match (self.as_str(), other.as_str()) {
+                (Some(a), Some(b)) => a.trim_end() == b.trim_end(),
+                (None, None) => self.as_i128().is_some() && self.as_i128() == other.as_i128(),
+                (Some(s), None) => s.trim_end() == other.to_string(),
+                (None, Some(s)) => self.to_string() == s.trim_end(),
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I128(n) => write!(f, "{}", n),
+            Answer::U128(n) => write!(f, "{}", n),
+            Answer::Text(s) | Answer::Rendered(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Integers that fit in an `i64` are stored as numbers, everything else as strings.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_i128().map(i64::try_from) {
+            Some(Ok(n)) => serializer.serialize_i64(n),
+            _ => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an integer or a string")
+            }
+
+            fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+                Ok(Answer::I64(n))
+            }
+
+            fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+                Ok(Answer::U64(n))
+            }
+
+            /// Integers too large for a TOML integer are stored as strings, so strings holding
+            /// an integer as it is printed are read back as numbers.
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+                let answer = if let Ok(n) = s.parse::<i128>() {
+                    Answer::I128(n)
+                } else if let Ok(n) = s.parse::<u128>() {
+                    Answer::U128(n)
+                } else {
+                    return Ok(Answer::from(s));
+                };
+
+                Ok(if answer.to_string() == s {
+                    answer
+                } else {
+                    Answer::from(s)
+                })
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(
+    i32 => I64,
+    i64 => I64,
+    isize => I64,
+    u32 => U64,
+    u64 => U64,
+    usize => U64,
+    i128 => I128,
+    u128 => U128,
+);
+
+/// Strings are kept as they are, even when they look like numbers.
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        if s.contains('\n') {
+            Answer::Rendered(s.to_string())
+        } else {
+            Answer::Text(s.to_string())
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::from(s.as_str())
+    }
+}
+
+#[test]
+fn test_answer_eq() {
+    assert_eq!(Answer::from(5_i32), Answer::from(5_u128));
+    assert_eq!(Answer::from(-5_i64), Answer::I128(-5));
+    assert_ne!(Answer::from(-5_i64), Answer::from(5_u64));
+    assert_eq!(Answer::U128(u128::MAX), Answer::from(u128::MAX.to_string()));
+    assert_ne!(Answer::U128(u128::MAX), Answer::I128(-1));
+    assert_ne!(Answer::from(5_i32), Answer::from("five"));
+    assert_eq!(Answer::from(5_i32), Answer::from("5"));
+    assert_ne!(Answer::from(7_i32), Answer::from("007"));
+    assert_ne!(Answer::from("+5"), Answer::from(5_i32));
+    assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_string()));
+    assert_eq!(
+        Answer::Rendered("#.\n.#\n".to_string()),
+        Answer::from("#.\n.#")
+    );
+}
+
+#[test]
+fn test_answer_text() {
+    for s in ["007", "+5", "CMZ"] {
+        assert_eq!(Answer::from(s), Answer::Text(s.to_string()));
+        assert_eq!(Answer::from(s).to_string(), s);
+    }
+}
+
+#[test]
+fn test_answer_serde() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Answers {
+        small: Answer,
+        large: Answer,
+        text: Answer,
+        rendered: Answer,
+    }
+
+    let answers = Answers {
+        small: Answer::from(24000_usize),
+        large: Answer::U128(u128::MAX),
+        text: Answer::from("CMZ"),
+        rendered: Answer::Rendered("##..\n..##\n".to_string()),
+    };
+
+    let toml = toml::to_string(&answers).unwrap();
+    assert!(toml.contains("small = 24000\n"));
+    assert!(toml.contains(&format!("large = \"{}\"\n", u128::MAX)));
+
+    let roundtrip: Answers = toml::from_str(&toml).unwrap();
+    assert_eq!(roundtrip, answers);
+    assert!(roundtrip.rendered.is_multiline());
+
+    assert!(matches!(
+        toml::from_str::<Answers>(&toml).unwrap().large,
+        Answer::U128(u128::MAX)
+    ));
+    let padded: Answer = serde_json::from_str("\"007\"").unwrap();
+    assert_eq!(padded.to_string(), "007");
+
+    let json = serde_json::to_string(&answers.text).unwrap();
+    assert_eq!(json, "\"CMZ\"");
+}
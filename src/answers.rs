@@ -0,0 +1,217 @@
+use std::{
+    fmt::{self, Display, Write},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answer::Answer,
+    runner::{self, DayRun},
+    solution::DynSolution,
+};
+
+/// The accepted answers of a day, as stored in `answers/dayN.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownAnswers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+pub fn path(day: u8) -> PathBuf {
+    PathBuf::from(format!("answers/day{}.toml", day))
+}
+
+/// Loads the accepted answers for `day`, if any were recorded.
+pub fn load(day: u8) -> Result<Option<KnownAnswers>> {
+    let path = path(day);
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents =
+        std::fs::read_to_string(&path).context(format!("Failed to read `{}`", path.display()))?;
+
+    toml::from_str(&contents)
+        .context(format!("Failed to parse `{}`", path.display()))
+        .map(Some)
+}
+
+pub fn store(day: u8, answers: &KnownAnswers) -> Result<()> {
+    let path = path(day);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    std::fs::write(&path, toml::to_string_pretty(answers)?)
+        .context(format!("Failed to write `{}`", path.display()))
+}
+
+#[derive(Debug)]
+pub enum Check {
+    Ok,
+    /// No accepted answer is recorded for this part.
+    Unknown(Answer),
+    Changed {
+        expected: Answer,
+        actual: Answer,
+    },
+    Failed(anyhow::Error),
+}
+
+impl Check {
+    fn new(expected: Option<&Answer>, actual: Result<Answer>) -> Self {
+        match (expected, actual) {
+            (_, Err(e)) => Check::Failed(e),
+            (None, Ok(actual)) => Check::Unknown(actual),
+            (Some(expected), Ok(actual)) if *expected == actual => Check::Ok,
+            (Some(expected), Ok(actual)) => Check::Changed {
+                expected: expected.clone(),
+                actual,
+            },
+        }
+    }
+
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Check::Changed { .. } | Check::Failed(_))
+    }
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: u8,
+    pub part1: Check,
+    pub part2: Check,
+}
+
+impl Verification {
+    /// Compares both parts of `run` against `known`. A run which failed before solving fails both
+    /// parts.
+    pub fn new(run: DayRun, known: &KnownAnswers) -> Self {
+        let (part1, part2) = match run.parts {
+            Ok([part1, part2]) => (part1.answer, part2.answer),
+            Err(e) => (Err(anyhow!("{:#}", e)), Err(anyhow!("{:#}", e))),
+        };
+
+        Self {
+            day: run.day,
+            part1: Check::new(known.part1.as_ref(), part1),
+            part2: Check::new(known.part2.as_ref(), part2),
+        }
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.part1.is_regression() || self.part2.is_regression()
+    }
+
+    /// The answers produced by this run, for recording them as accepted.
+    pub fn accepted(&self, previous: KnownAnswers) -> KnownAnswers {
+        let accept = |check: &Check, previous: Option<Answer>| match check {
+            Check::Unknown(actual) | Check::Changed { actual, .. } => Some(actual.clone()),
+            Check::Ok | Check::Failed(_) => previous,
+        };
+
+        KnownAnswers {
+            part1: accept(&self.part1, previous.part1),
+            part2: accept(&self.part2, previous.part2),
+        }
+    }
+}
+
+/// Prefixes every line of `s`, so multi-line answers line up in the diff.
+fn prefixed(f: &mut fmt::Formatter<'_>, prefix: &str, s: &str) -> fmt::Result {
+    for line in s.lines() {
+        f.write_str(prefix)?;
+        f.write_str(line)?;
+        f.write_char('\n')?;
+    }
+
+    Ok(())
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (part, check) in [(1, &self.part1), (2, &self.part2)] {
+            write!(f, "Day {} part {}: ", self.day, part)?;
+
+            match check {
+                Check::Ok => writeln!(f, "ok")?,
+                Check::Unknown(actual) => {
+                    writeln!(f, "no accepted answer")?;
+                    prefixed(f, "  ? ", &actual.to_string())?;
+                }
+                Check::Changed { expected, actual } => {
+                    writeln!(f, "changed")?;
+                    prefixed(f, "  - ", &expected.to_string())?;
+                    prefixed(f, "  + ", &actual.to_string())?;
+                }
+                Check::Failed(e) => writeln!(f, "failed: {:#}", e)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Solves `day` with `input` and compares both parts against `known`.
+///
+/// A panic while parsing or solving fails that step, like in [`runner::solve`].
+pub fn verify(solution: &dyn DynSolution, input: &str, known: &KnownAnswers) -> Verification {
+    Verification::new(runner::solve(solution, input), known)
+}
+
+#[test]
+fn test_known_answers_toml() {
+    let answers: KnownAnswers = toml::from_str("part1 = 24000\npart2 = \"CMZ\"\n").unwrap();
+
+    assert_eq!(answers.part1, Some(Answer::from(24000)));
+    assert_eq!(answers.part2, Some(Answer::from("CMZ")));
+
+    let roundtrip: KnownAnswers = toml::from_str(&toml::to_string(&answers).unwrap()).unwrap();
+    assert_eq!(roundtrip, answers);
+}
+
+#[test]
+fn test_verify() {
+    let input = "1000\n2000\n\n4000";
+    let known = KnownAnswers {
+        part1: Some(Answer::from(4000)),
+        part2: Some(Answer::from(1)),
+    };
+
+    let verification = verify(&crate::days::day1::Day1, input, &known);
+
+    assert!(matches!(verification.part1, Check::Ok));
+    assert!(matches!(verification.part2, Check::Changed { .. }));
+    assert!(verification.is_regression());
+    assert_eq!(
+        verification.to_string(),
+        "Day 1 part 1: ok\nDay 1 part 2: changed\n  - 1\n  + 7000\n"
+    );
+
+    let accepted = verification.accepted(known);
+    assert_eq!(accepted.part2, Some(Answer::from(7000)));
+
+    let verification = verify(&crate::days::day1::Day1, input, &accepted);
+    assert!(!verification.is_regression());
+}
+
+#[test]
+fn test_verify_failed_run() {
+    let known = KnownAnswers {
+        part1: Some(Answer::from(4000)),
+        part2: None,
+    };
+    let run = DayRun::failed(1, anyhow!("No input for day 1"));
+    let verification = Verification::new(run, &known);
+
+    assert!(verification.is_regression());
+    assert_eq!(
+        verification.to_string(),
+        "Day 1 part 1: failed: No input for day 1\nDay 1 part 2: failed: No input for day 1\n"
+    );
+    assert_eq!(verification.accepted(known.clone()), known);
+}
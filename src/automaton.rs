@@ -0,0 +1,308 @@
+//! Cellular automata over sparse grids.
+//!
+//! Every generation, a rule decides the next value of every occupied cell and of the empty cells
+//! next to them, from what it sees around the cell.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::grid::{InfiniteGrid, Point, Rect, Storage, ADJACENT};
+
+/// What a rule sees of a cell.
+pub struct Neighbourhood<'a, T, S> {
+    pub point: Point,
+    grid: &'a InfiniteGrid<T, S>,
+}
+
+impl<'a, T, S: Storage<T>> Neighbourhood<'a, T, S> {
+    /// The value of the cell itself.
+    pub fn value(&self) -> Option<&'a T> {
+        self.grid.get(self.point)
+    }
+
+    /// The value of the cell at `offset` from this one.
+    pub fn at(&self, offset: Point) -> Option<&'a T> {
+        self.grid.get(self.point + offset)
+    }
+
+    /// The occupied orthogonal and diagonal neighbours.
+    pub fn neighbours(&self) -> impl Iterator<Item = (Point, &'a T)> {
+        self.grid.neighbours8(self.point)
+    }
+
+    /// The number of occupied orthogonal and diagonal neighbours.
+    pub fn live_neighbours(&self) -> usize {
+        self.neighbours().count()
+    }
+}
+
+/// How the cells of a generation are updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Every cell sees the previous generation.
+    Simultaneous,
+    /// Cells are updated one at a time, row by row, each seeing the cells updated before it.
+    ///
+    /// A cell occupied during the generation makes its empty neighbours later in the order
+    /// candidates of the same generation.
+    Sequential,
+}
+
+/// Statistics of the state after a generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generation {
+    /// Counted from 1.
+    pub number: usize,
+    /// The number of occupied cells.
+    pub live: usize,
+    pub bounds: Option<Rect>,
+    /// The number of cells whose value changed.
+    pub changed: usize,
+}
+
+/// A sparse grid evolving according to a rule, returning the next value of a cell or `None` to
+/// leave it empty.
+pub struct Automaton<T, R, S = HashMap<Point, T>> {
+    pub grid: InfiniteGrid<T, S>,
+    /// The next generation while it is built by [`Update::Simultaneous`], reused every step.
+    next: InfiniteGrid<T, S>,
+    rule: R,
+    update: Update,
+    /// Statistics of every generation so far.
+    pub generations: Vec<Generation>,
+}
+
+impl<T, R, S> Automaton<T, R, S>
+where
+    T: PartialEq,
+    R: Fn(&Neighbourhood<'_, T, S>) -> Option<T>,
+    S: Storage<T>,
+{
+    pub fn new(grid: InfiniteGrid<T, S>, update: Update, rule: R) -> Self {
+        Self {
+            grid,
+            next: InfiniteGrid::default(),
+            rule,
+            update,
+            generations: vec![],
+        }
+    }
+
+    /// The occupied cells and the cells next to them.
+    fn candidates(&self) -> HashSet<Point> {
+        self.grid
+            .iter()
+            .flat_map(|(p, _)| ADJACENT.iter().map(move |&d| p + d).chain([p]))
+            .collect()
+    }
+
+    /// Runs a single generation, returning its statistics.
+    pub fn step(&mut self) -> &Generation {
+        let candidates = self.candidates();
+        let mut changed = 0;
+
+        match self.update {
+            Update::Simultaneous => {
+                self.next.clear();
+
+                for point in candidates {
+                    let value = (self.rule)(&Neighbourhood {
+                        point,
+                        grid: &self.grid,
+                    });
+
+                    if value.as_ref() != self.grid.get(point) {
+                        changed += 1;
+                    }
+
+                    if let Some(value) = value {
+                        self.next.insert(point, value);
+                    }
+                }
+
+                std::mem::swap(&mut self.grid, &mut self.next);
+            }
+            Update::Sequential => {
+                // Row by row, as `(y, x)`.
+                let mut pending = candidates
+                    .into_iter()
+                    .map(|p| (p.y, p.x))
+                    .collect::<BTreeSet<_>>();
+
+                while let Some((y, x)) = pending.pop_first() {
+                    let point = Point::new(x, y);
+                    let value = (self.rule)(&Neighbourhood {
+                        point,
+                        grid: &self.grid,
+                    });
+
+                    if value.as_ref() == self.grid.get(point) {
+                        continue;
+                    }
+
+                    changed += 1;
+
+                    match value {
+                        Some(value) => {
+                            self.grid.insert(point, value);
+                            pending.extend(
+                                ADJACENT
+                                    .iter()
+                                    .map(|&d| point + d)
+                                    .map(|n| (n.y, n.x))
+                                    .filter(|&n| n > (y, x)),
+                            );
+                        }
+                        None => {
+                            self.grid.remove(point);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.generations.push(Generation {
+            number: self.generations.len() + 1,
+            live: self.grid.num_points(),
+            bounds: self.grid.dimensions(),
+            changed,
+        });
+
+        self.generations.last().expect("just pushed")
+    }
+
+    /// Runs `generations` more generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Runs until a generation changes nothing, returning its number. `None` if the state still
+    /// changes after `limit` more generations.
+    pub fn run_until_stable(&mut self, limit: usize) -> Option<usize> {
+        for _ in 0..limit {
+            let generation = self.step();
+
+            if generation.changed == 0 {
+                return Some(generation.number);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(points: &[(i32, i32)]) -> InfiniteGrid<()> {
+        let mut grid = InfiniteGrid::new();
+
+        for &p in points {
+            grid.insert(p.into(), ());
+        }
+
+        grid
+    }
+
+    fn life(cell: &Neighbourhood<'_, (), HashMap<Point, ()>>) -> Option<()> {
+        match (cell.value(), cell.live_neighbours()) {
+            (Some(_), 2 | 3) | (None, 3) => Some(()),
+            _ => None,
+        }
+    }
+
+    fn points(grid: &InfiniteGrid<()>) -> Vec<(i32, i32)> {
+        let mut points = grid.iter().map(|(p, _)| (p.x, p.y)).collect::<Vec<_>>();
+        points.sort_by_key(|&(x, y)| (y, x));
+        points
+    }
+
+    #[test]
+    fn test_life() {
+        let blinker = grid(&[(0, 1), (1, 1), (2, 1)]);
+        let mut automaton = Automaton::new(blinker, Update::Simultaneous, life);
+
+        automaton.step();
+        assert_eq!(points(&automaton.grid), [(1, 0), (1, 1), (1, 2)]);
+
+        automaton.step();
+        assert_eq!(points(&automaton.grid), [(0, 1), (1, 1), (2, 1)]);
+
+        // A blinker never settles.
+        assert_eq!(automaton.run_until_stable(10), None);
+        assert!(automaton
+            .generations
+            .iter()
+            .all(|g| g.live == 3 && g.changed == 4));
+
+        let glider = grid(&[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        let mut automaton = Automaton::new(glider, Update::Simultaneous, life);
+        automaton.run(4);
+
+        assert_eq!(
+            points(&automaton.grid),
+            [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)]
+        );
+        assert_eq!(
+            automaton.generations[3],
+            Generation {
+                number: 4,
+                live: 5,
+                bounds: Some(Rect::new(Point::new(1, 1), Point::new(3, 3))),
+                changed: 4,
+            }
+        );
+
+        // The grids are reused from one generation to the next.
+        automaton.run(36);
+        assert_eq!(
+            points(&automaton.grid),
+            [(11, 10), (12, 11), (10, 12), (11, 12), (12, 12)]
+        );
+        assert_eq!(
+            automaton.grid.dimensions(),
+            Some(Rect::new(Point::new(10, 10), Point::new(12, 12)))
+        );
+
+        let block = grid(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        let mut automaton = Automaton::new(block, Update::Simultaneous, life);
+        assert_eq!(automaton.run_until_stable(10), Some(1));
+    }
+
+    #[test]
+    fn test_sequential() {
+        // Cells die when the cell to their west is occupied.
+        let rule = |cell: &Neighbourhood<'_, (), HashMap<Point, ()>>| {
+            (cell.value().is_some() && cell.at(Point::new(-1, 0)).is_none()).then_some(())
+        };
+
+        let row = [(0, 0), (1, 0), (2, 0)];
+
+        let mut simultaneous = Automaton::new(grid(&row), Update::Simultaneous, rule);
+        assert_eq!(simultaneous.run_until_stable(10), Some(2));
+        assert_eq!(points(&simultaneous.grid), [(0, 0)]);
+
+        // Once the second cell is gone, the third one survives.
+        let mut sequential = Automaton::new(grid(&row), Update::Sequential, rule);
+        assert_eq!(sequential.run_until_stable(10), Some(2));
+        assert_eq!(points(&sequential.grid), [(0, 0), (2, 0)]);
+        assert_eq!(sequential.generations[0].changed, 1);
+
+        // Empty cells grow east of occupied ones, up to x = 2.
+        let rule = |cell: &Neighbourhood<'_, (), HashMap<Point, ()>>| {
+            let grows = cell.point.x <= 2 && cell.at(Point::new(-1, 0)).is_some();
+            (cell.value().is_some() || grows).then_some(())
+        };
+
+        let mut simultaneous = Automaton::new(grid(&[(0, 0)]), Update::Simultaneous, rule);
+        simultaneous.step();
+        assert_eq!(points(&simultaneous.grid), [(0, 0), (1, 0)]);
+
+        // Cells occupied during a generation make their neighbours candidates right away.
+        let mut sequential = Automaton::new(grid(&[(0, 0)]), Update::Sequential, rule);
+        sequential.step();
+        assert_eq!(points(&sequential.grid), [(0, 0), (1, 0), (2, 0)]);
+    }
+}
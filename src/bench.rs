@@ -0,0 +1,247 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde::{Serialize, Serializer};
+
+use crate::{solution::DynSolution, timed};
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Number of untimed runs before sampling.
+    pub warmup: usize,
+    /// Exact number of samples to take. When unset, sample until `budget` is exhausted.
+    pub iterations: Option<usize>,
+    /// Time budget per measured phase.
+    pub budget: Duration,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: None,
+            budget: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Summary statistics over the samples of a single phase.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub samples: usize,
+    #[serde(rename = "min_ns", serialize_with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "p95_ns", serialize_with = "nanos")]
+    pub p95: Duration,
+    #[serde(rename = "stddev_ns", serialize_with = "nanos")]
+    pub stddev: Duration,
+}
+
+fn nanos<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos() as u64)
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort();
+        let n = samples.len();
+
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        // Nearest-rank percentile.
+        let p95 = samples[((n as f64 * 0.95).ceil() as usize).max(1) - 1];
+
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples
+                .iter()
+                .map(|s| (s.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayBench {
+    pub day: u8,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    /// The alternative implementations of the parts, by name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub variants: BTreeMap<&'static str, Stats>,
+}
+
+impl Display for DayBench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+        .into_iter()
+        .chain(self.variants.iter().map(|(&name, stats)| (name, stats)))
+        .collect::<Vec<_>>();
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+        writeln!(f, "Day {}", self.day)?;
+        writeln!(
+            f,
+            "{:width$} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "", "samples", "min", "median", "mean", "p95", "stddev"
+        )?;
+
+        for (name, stats) in rows {
+            writeln!(
+                f,
+                "{:width$} {:>8} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+                name, stats.samples, stats.min, stats.median, stats.mean, stats.p95, stats.stddev
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs `f` according to `options`, returning the duration of every sampled run.
+pub fn sample<R>(options: &BenchOptions, f: impl Fn() -> Result<R>) -> Result<Stats> {
+    for _ in 0..options.warmup {
+        f()?;
+    }
+
+    let mut samples = vec![];
+    let started = Instant::now();
+
+    loop {
+        let (result, took) = timed(&f);
+        result?;
+        samples.push(took);
+
+        let done = match options.iterations {
+            Some(n) => samples.len() >= n,
+            None => started.elapsed() >= options.budget,
+        };
+
+        if done {
+            break;
+        }
+    }
+
+    Ok(Stats::from_samples(samples).expect("at least one sample"))
+}
+
+/// Benchmarks parsing, both parts and every variant of `solution` separately.
+pub fn bench(solution: &dyn DynSolution, input: &str, options: &BenchOptions) -> Result<DayBench> {
+    let day = solution.day();
+    let parse = sample(options, || solution.parse(input)).context("Failed to parse input")?;
+
+    let parsed = solution.parse(input)?;
+    let parsed: &dyn Any = parsed.as_ref();
+
+    let part1 = sample(options, || solution.part1(parsed)).context("Part 1 failed")?;
+    let part2 = sample(options, || solution.part2(parsed)).context("Part 2 failed")?;
+
+    let mut variants = BTreeMap::new();
+    for (name, run) in solution.variants() {
+        let stats = sample(options, || run(parsed)).with_context(|| format!("{} failed", name))?;
+        variants.insert(name, stats);
+    }
+
+    Ok(DayBench {
+        day,
+        parse,
+        part1,
+        part2,
+        variants,
+    })
+}
+
+#[test]
+fn test_stats() {
+    let samples = [5, 1, 4, 2, 3, 100]
+        .into_iter()
+        .map(Duration::from_millis)
+        .collect();
+
+    let stats = Stats::from_samples(samples).unwrap();
+
+    assert_eq!(stats.samples, 6);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_micros(3500));
+    assert_eq!(stats.p95, Duration::from_millis(100));
+    assert_eq!(stats.mean.as_millis(), 19);
+    assert_eq!(stats.stddev.as_millis(), 39);
+
+    let single = Stats::from_samples(vec![Duration::from_millis(7)]).unwrap();
+    assert_eq!(single.median, Duration::from_millis(7));
+    assert_eq!(single.stddev, Duration::ZERO);
+
+    assert!(Stats::from_samples(vec![]).is_none());
+}
+
+#[test]
+fn test_bench_iterations() {
+    let options = BenchOptions {
+        warmup: 0,
+        iterations: Some(4),
+        budget: Duration::ZERO,
+    };
+
+    let result = bench(&crate::days::day1::Day1, "1\n2\n\n3", &options).unwrap();
+
+    assert_eq!(result.day, 1);
+    assert_eq!(result.parse.samples, 4);
+    assert_eq!(result.part2.samples, 4);
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["part1"]["samples"], 4);
+    assert!(json["parse"]["median_ns"].is_u64());
+    assert!(json.get("variants").is_none());
+}
+
+#[test]
+fn test_bench_variants() {
+    let options = BenchOptions {
+        warmup: 0,
+        iterations: Some(2),
+        budget: Duration::ZERO,
+    };
+
+    let result = bench(&crate::days::day9::Day9, "R 4\nU 4\nL 3", &options).unwrap();
+
+    assert_eq!(
+        result.variants.keys().copied().collect::<Vec<_>>(),
+        ["part1 (HashMap)", "part2 (HashMap)"]
+    );
+    assert!(result.variants.values().all(|stats| stats.samples == 2));
+    assert!(result.to_string().contains("part2 (HashMap)"));
+}
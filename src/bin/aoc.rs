@@ -0,0 +1,308 @@
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Result};
+use aoc2022::{
+    answers::{self, Verification},
+    bench::{self, BenchOptions},
+    days::{day9, Selection},
+    inputs::{self, InputCache, Session},
+    report,
+    runner::{self, DayRun, InputArgs},
+    scaffold,
+    solution::{DynSolution, Solution},
+    summary::Summary,
+};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the selected days against `input/dayN`.
+    ///
+    /// A single day reads its input from stdin when it is piped in. Several days are run in
+    /// parallel, followed by a summary table.
+    Run {
+        /// A day (`5`), a range of days (`3..=7`, `3..7`) or `all`.
+        days: Selection,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Print a machine-readable report with one record per part instead.
+        #[arg(long, value_enum)]
+        report: Option<report::Format>,
+
+        /// Worker threads for running several days. Defaults to the number of CPUs.
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+    /// Benchmark parsing and both parts of the selected days.
+    Bench {
+        /// A day (`5`), a range of days (`3..=7`, `3..7`) or `all`.
+        days: Selection,
+
+        /// Untimed runs before sampling.
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+
+        /// Take exactly this many samples instead of sampling for `--budget-ms`.
+        #[arg(long)]
+        iterations: Option<usize>,
+
+        /// Time budget per phase, in milliseconds.
+        #[arg(long, default_value_t = 1000)]
+        budget_ms: u64,
+
+        /// Print the results as JSON.
+        #[arg(long)]
+        json: bool,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Record the rope of day 9 as one image per step, to be assembled into a video.
+    Frames {
+        /// The directory to write `frame_NNNNN.ppm` files to.
+        dir: PathBuf,
+
+        /// The number of knots of the rope, at least 2.
+        #[arg(long, default_value_t = 10)]
+        knots: usize,
+
+        /// Pixels per cell.
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Download the input of a day into `input/dayN`.
+    Download {
+        day: u8,
+
+        /// Replace an input even if it was edited locally.
+        #[arg(long)]
+        force: bool,
+
+        /// The site to download from.
+        #[arg(long, default_value = inputs::DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// Create the solution module, binary and example input of a new day, and register it.
+    New { day: u8 },
+    /// Check the selected days against the answers recorded in `answers/dayN.toml`.
+    Verify {
+        /// A day (`5`), a range of days (`3..=7`, `3..7`) or `all`.
+        #[arg(default_value = "all")]
+        days: Selection,
+
+        /// Record the current answers as accepted.
+        #[arg(long)]
+        accept: bool,
+    },
+}
+
+/// The selected days, checking that `--input` is only given for a single day.
+fn solutions(days: &Selection, input: &InputArgs) -> Result<Vec<&'static dyn DynSolution>> {
+    let solutions = days.solutions()?;
+
+    if input.input.is_some() && solutions.len() > 1 {
+        bail!("`--input` can only be used with a single day");
+    }
+
+    Ok(solutions)
+}
+
+/// The input of every solution, and a failed run for every day whose input cannot be read.
+fn read_inputs(
+    solutions: Vec<&'static dyn DynSolution>,
+    input: &InputArgs,
+) -> (Vec<(&'static dyn DynSolution, String)>, Vec<DayRun>) {
+    let mut inputs = vec![];
+    let mut failed = vec![];
+
+    for solution in solutions {
+        match input.read(solution.day()) {
+            Ok(input) => inputs.push((solution, input)),
+            Err(e) => failed.push(DayRun::failed(solution.day(), e)),
+        }
+    }
+
+    (inputs, failed)
+}
+
+/// The number of worker threads, defaulting to the number of CPUs.
+fn threads(jobs: Option<usize>) -> usize {
+    jobs.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, NonZeroUsize::get))
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    match Cli::parse().command {
+        Command::Run {
+            days,
+            input,
+            report: None,
+            jobs,
+        } => {
+            let solutions = solutions(&days, &input)?;
+
+            if let [solution] = solutions[..] {
+                let input = input.read_or_stdin(solution.day())?;
+
+                println!("Day {}", solution.day());
+                return runner::run(solution, &input);
+            }
+
+            let started = Instant::now();
+
+            let (inputs, mut runs) = read_inputs(solutions, &input);
+            runs.extend(runner::solve_all(&inputs, threads(jobs)));
+            runs.sort_by_key(|run| run.day);
+
+            let summary = Summary {
+                total: started.elapsed(),
+                rows: runs
+                    .into_iter()
+                    .map(|run| {
+                        let known = answers::load(run.day)?.unwrap_or_default();
+                        Ok((run, known))
+                    })
+                    .collect::<Result<_>>()?,
+            };
+
+            println!("{}", summary);
+
+            if summary.failures() > 0 {
+                bail!("{} part(s) failed or changed", summary.failures());
+            }
+        }
+        Command::Run {
+            days,
+            input,
+            report: Some(format),
+            jobs,
+        } => {
+            let revision = report::git_revision();
+            let timestamp = report::now();
+
+            let (inputs, failed) = read_inputs(solutions(&days, &input)?, &input);
+            let mut runs = runner::solve_all(&inputs, threads(jobs))
+                .into_iter()
+                .zip(inputs.iter().map(|(_, input)| Some(input.as_str())))
+                .chain(failed.into_iter().map(|run| (run, None)))
+                .collect::<Vec<_>>();
+            runs.sort_by_key(|(run, _)| run.day);
+
+            let records = runs
+                .iter()
+                .flat_map(|(run, input)| {
+                    report::records(run, *input, revision.as_deref(), timestamp)
+                })
+                .collect::<Vec<_>>();
+
+            report::write(format, &records, std::io::stdout().lock())?;
+        }
+        Command::Bench {
+            days,
+            warmup,
+            iterations,
+            budget_ms,
+            json,
+            input,
+        } => {
+            let options = BenchOptions {
+                warmup,
+                iterations,
+                budget: Duration::from_millis(budget_ms),
+            };
+
+            let mut results = vec![];
+
+            for solution in solutions(&days, &input)? {
+                let input = input.read(solution.day())?;
+                let result = bench::bench(solution, &input, &options)?;
+
+                if !json {
+                    println!("{}", result);
+                }
+
+                results.push(result);
+            }
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+            }
+        }
+        Command::Frames {
+            dir,
+            knots,
+            scale,
+            input,
+        } => {
+            if knots < 2 {
+                bail!("A rope needs at least 2 knots");
+            }
+
+            let input = day9::Day9::parse_input(&input.read(day9::Day9::DAY)?)?;
+            let frames = day9::record_frames(&input, knots, &dir, scale)?;
+
+            println!("Wrote {} frames to `{}`", frames, dir.display());
+        }
+        Command::Download {
+            day,
+            force,
+            base_url,
+        } => {
+            let cache = InputCache::with_fetcher("input", base_url, inputs::HttpFetcher);
+            let fetched = cache.fetch(day, &Session::from_env()?, force)?;
+
+            println!("`{}`: {}", cache.input_path(day).display(), fetched);
+        }
+        Command::New { day } => {
+            for path in scaffold::new_day(Path::new("."), day)? {
+                println!("Created `{}`", path.display());
+            }
+        }
+        Command::Verify { days, accept } => {
+            let mut regressions = 0;
+
+            for solution in days.solutions()? {
+                let known = answers::load(solution.day())?.unwrap_or_default();
+                let run = match runner::read_input(solution.day()) {
+                    Ok(input) => runner::solve(solution, &input),
+                    Err(e) => DayRun::failed(solution.day(), e),
+                };
+                let verification = Verification::new(run, &known);
+
+                print!("{}", verification);
+
+                if accept {
+                    answers::store(solution.day(), &verification.accepted(known))?;
+                } else if verification.is_regression() {
+                    regressions += 1;
+                }
+            }
+
+            if regressions > 0 {
+                bail!(
+                    "{} day(s) no longer match their accepted answers",
+                    regressions
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
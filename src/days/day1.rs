@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+use crate::{
+    parse::{int, Span},
+    solution::Solution,
+};
+
+type Input = Vec<Vec<i32>>;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Input;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        let per_elf = Span::new(input)
+            .blocks()
+            .map(|elf| elf.parse_lines(int))
+            .collect::<Result<_, _>>()?;
+
+        Ok(per_elf)
+    }
+
+    fn part1(input: &Input) -> Result<i32> {
+        input
+            .iter()
+            .map(|elf| elf.iter().sum())
+            .max()
+            .context("Empty input")
+    }
+
+    fn part2(input: &Input) -> Result<i32> {
+        Ok(input
+            .iter()
+            .map(|elf| elf.iter().sum::<i32>())
+            .sorted()
+            .rev()
+            .take(3)
+            .sum())
+    }
+}
+
+#[test]
+fn test() {
+    let input = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    let parsed = Day1::parse_input(input).unwrap();
+
+    assert_eq!(Day1::part1(&parsed).unwrap(), 24000);
+    assert_eq!(Day1::part2(&parsed).unwrap(), 45000);
+}
@@ -0,0 +1,166 @@
+use std::{cell::RefCell, collections::VecDeque, ops::AddAssign, rc::Rc};
+
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, value},
+    error::context,
+    sequence::preceded,
+};
+
+use crate::{
+    answer::Answer,
+    parse::{int, IResult, Span},
+    solution::Solution,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+type Input = VecDeque<Instruction>;
+
+fn instruction(s: &str) -> IResult<'_, Instruction> {
+    context(
+        "one of `noop`, `addx <n>`",
+        alt((
+            value(Instruction::Noop, tag("noop")),
+            map(preceded(tag("addx "), int), Instruction::Addx),
+        )),
+    )(s)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Input;
+    type Output1 = i32;
+    type Output2 = Answer;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        Ok(Span::new(input).parse_lines(instruction)?.into())
+    }
+
+    fn part1(input: &Input) -> Result<i32> {
+        let mut cpu = Cpu::new();
+
+        let result = Rc::new(RefCell::new(0));
+
+        let clone = Rc::clone(&result);
+
+        cpu.simulate_program_for(
+            input.to_owned(),
+            220,
+            Box::new(move |register, clock| {
+                if [20, 60, 100, 140, 180, 220].contains(&clock) {
+                    clone.borrow_mut().add_assign(clock as i32 * register);
+                }
+            }),
+        );
+
+        let b = result.borrow();
+        Ok(b.to_owned())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        let mut cpu = Cpu::new();
+
+        // The sprite starts at position 1, so the first pixel is always lit.
+        let screen = Rc::new(RefCell::new(String::from("#")));
+
+        let clone = Rc::clone(&screen);
+
+        cpu.simulate_program_for(
+            input.to_owned(),
+            240,
+            Box::new(move |register, clock| {
+                let mut screen = clone.borrow_mut();
+
+                if clock % 40 == 0 {
+                    screen.push('\n');
+                }
+
+                // `register` is the value during the next cycle, which draws pixel `clock % 40`.
+                if clock < 240 {
+                    if (register - 1..=register + 1).contains(&(clock as i32 % 40)) {
+                        screen.push('#');
+                    } else {
+                        screen.push('.');
+                    }
+                }
+            }),
+        );
+
+        let b = screen.borrow();
+        Ok(Answer::Rendered(b.to_owned()))
+    }
+}
+
+struct Cpu {
+    register: i32,
+    clock: usize,
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Self {
+            register: 1,
+            clock: 1,
+        }
+    }
+
+    pub fn simulate_program_for(
+        &mut self,
+        mut program: VecDeque<Instruction>,
+        until: usize,
+        mut hook: Box<dyn FnMut(i32, usize)>,
+    ) {
+        let mut running_for = 1;
+        let mut current_instruction = program.pop_front();
+
+        while self.clock < until {
+            match current_instruction.as_ref() {
+                Some(Instruction::Addx(n)) if running_for == 2 => {
+                    self.register += n;
+                    running_for = 0;
+                    current_instruction = program.pop_front();
+                }
+                Some(Instruction::Noop) => {
+                    running_for = 0;
+                    current_instruction = program.pop_front();
+                }
+                _ => {}
+            }
+
+            hook(self.register, self.clock);
+
+            self.clock += 1;
+            running_for += 1;
+        }
+
+        hook(self.register, self.clock);
+    }
+}
+
+#[test]
+fn test_day10() {
+    let input = include_str!("../../input/day10_test");
+
+    let parsed = Day10::parse_input(input).unwrap();
+    assert_eq!(Day10::part1(&parsed).unwrap(), 13140);
+    assert_eq!(
+        Day10::part2(&parsed).unwrap().to_string(),
+        "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+    );
+}
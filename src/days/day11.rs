@@ -0,0 +1,338 @@
+use std::collections::VecDeque;
+
+use anyhow::{ensure, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{consumed, map, value, verify},
+    error::context,
+    sequence::{delimited, pair, preceded},
+};
+
+use crate::{
+    parse::{int, list, ws, IResult, Span},
+    solution::Solution,
+};
+
+type Input = Vec<Monkey>;
+
+type Int = i64;
+type MonkeyId = Int;
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: Vec<Int>,
+    operation: Expr,
+    test_divisible_by: Int,
+    forward: (MonkeyId, MonkeyId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Old,
+    Const(Int),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expr {
+    Add(Operand, Operand),
+    Multiply(Operand, Operand),
+}
+
+impl Operand {
+    fn eval(self, old: Int) -> Int {
+        match self {
+            Operand::Old => old,
+            Operand::Const(n) => n,
+        }
+    }
+}
+
+impl Expr {
+    fn eval(self, old: Int) -> Int {
+        match self {
+            Expr::Add(a, b) => a.eval(old) + b.eval(old),
+            Expr::Multiply(a, b) => a.eval(old) * b.eval(old),
+        }
+    }
+}
+
+fn operand(s: &str) -> IResult<'_, Operand> {
+    context(
+        "`old` or an integer",
+        ws(alt((
+            map(int, Operand::Const),
+            value(Operand::Old, tag("old")),
+        ))),
+    )(s)
+}
+
+fn expr(s: &str) -> IResult<'_, Expr> {
+    let (rest, lhs) = operand(s)?;
+    let (rest, op) = ws(alt((char('+'), char('*'))))(rest)?;
+    let (rest, rhs) = operand(rest)?;
+
+    Ok((
+        rest,
+        match op {
+            '+' => Expr::Add(lhs, rhs),
+            '*' => Expr::Multiply(lhs, rhs),
+            _ => unreachable!(),
+        },
+    ))
+}
+
+/// `new = <expr>`.
+fn operation(s: &str) -> IResult<'_, Expr> {
+    preceded(pair(ws(tag("new")), char('=')), expr)(s)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Input;
+    type Output1 = Int;
+    type Output2 = Int;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        let mut monkeys = vec![];
+        let mut targets = vec![];
+
+        for block in Span::new(input).blocks() {
+            let header = block
+                .lines()
+                .next()
+                .ok_or_else(|| block.error_at_end("`Monkey <id>:`"))?;
+            let (token, id) = header.trim().parse(context(
+                "`Monkey <id>:`",
+                delimited(tag("Monkey "), consumed(int::<MonkeyId>), char(':')),
+            ))?;
+            // Monkeys are known by their position, so the ids have to count up from 0.
+            if id != monkeys.len() as MonkeyId {
+                return Err(block
+                    .error(token, format!("monkey {}", monkeys.len()))
+                    .into());
+            }
+
+            let notes = block.record()?;
+
+            let items =
+                notes.value("Starting items", context("a list of items", list(',', int)))?;
+            let operation = notes.value(
+                "Operation",
+                context("an operation like `new = old * 19`", operation),
+            )?;
+            let divisible_by = notes.value(
+                "Test",
+                context(
+                    "`divisible by <n>`",
+                    preceded(
+                        tag("divisible by "),
+                        context("a positive divisor", verify(int, |&n: &Int| n > 0)),
+                    ),
+                ),
+            )?;
+            // Targets are checked once every monkey is known.
+            let throw_to = |key| {
+                notes.value(
+                    key,
+                    context(
+                        "`throw to monkey <id>`",
+                        preceded(tag("throw to monkey "), consumed(int::<MonkeyId>)),
+                    ),
+                )
+            };
+            let forward = (throw_to("If true")?, throw_to("If false")?);
+            targets.extend([forward.0, forward.1].map(|(token, _)| (monkeys.len(), token)));
+
+            monkeys.push(Monkey {
+                items,
+                operation,
+                test_divisible_by: divisible_by,
+                forward: (forward.0 .1, forward.1 .1),
+            });
+        }
+
+        let span = Span::new(input);
+        for (from, token) in targets {
+            let target = token.parse::<MonkeyId>().expect("parsed as an id");
+
+            if !(0..monkeys.len() as MonkeyId).contains(&target) || target == from as MonkeyId {
+                return Err(span
+                    .error(
+                        token,
+                        format!(
+                            "a monkey other than {} between 0 and {}",
+                            from,
+                            monkeys.len() as MonkeyId - 1
+                        ),
+                    )
+                    .into());
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part1(input: &Input) -> Result<Int> {
+        monkey_business(input, 20, |worry| worry / 3)
+    }
+
+    fn part2(input: &Input) -> Result<Int> {
+        // Every test still gives the same result modulo the product of all divisors.
+        let mod_all: Int = input.iter().map(|m| m.test_divisible_by).product();
+
+        monkey_business(input, 10_000, |worry| worry % mod_all)
+    }
+}
+
+/// The product of the number of items inspected by the two busiest monkeys after `rounds` rounds,
+/// with `relief` applied to the worry level of every inspected item.
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: impl Fn(Int) -> Int) -> Result<Int> {
+    ensure!(monkeys.len() >= 2, "There need to be at least 2 monkeys");
+
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.iter().copied().collect::<VecDeque<_>>())
+        .collect::<Vec<_>>();
+    let mut inspected = vec![0; monkeys.len()];
+
+    for _ in 0..rounds {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            while let Some(item) = items[id].pop_front() {
+                inspected[id] += 1;
+
+                let new = relief(monkey.operation.eval(item));
+                let (if_true, if_false) = monkey.forward;
+                let target = if new % monkey.test_divisible_by == 0 {
+                    if_true
+                } else {
+                    if_false
+                };
+
+                items[target as usize].push_back(new);
+            }
+        }
+    }
+
+    inspected.sort_unstable_by(|a, b| b.cmp(a));
+
+    Ok(inspected[0] * inspected[1])
+}
+
+#[test]
+fn test_day11() {
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+    ";
+
+    let parsed = Day11::parse_input(input).unwrap();
+
+    assert_eq!(Day11::part1(&parsed).unwrap(), 10605);
+    assert_eq!(Day11::part2(&parsed).unwrap(), 2713310158);
+}
+
+#[test]
+fn test_day11_parse_error() {
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2";
+
+    let e = Day11::parse_input(input).unwrap_err();
+    let e = e.downcast::<crate::parse::ParseError>().unwrap();
+
+    assert_eq!(e.line, 5);
+    assert_eq!(e.expected, "a `If false:` line");
+}
+
+#[test]
+fn test_day11_invalid_notes() {
+    let monkey = |operation: &str, divisor: &str, target: &str| {
+        format!(
+            "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = {}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+",
+            operation, divisor, target
+        )
+    };
+    let error = |input: String| {
+        let e = Day11::parse_input(&input).unwrap_err();
+        let e = e.downcast::<crate::parse::ParseError>().unwrap();
+        (e.line, e.column, e.expected)
+    };
+
+    assert!(Day11::parse_input(&monkey("old * 19", "23", "1")).is_ok());
+
+    assert_eq!(
+        error(monkey("old * 19 junk", "23", "1")),
+        (3, 29, "end of input".to_string())
+    );
+    assert_eq!(
+        error(monkey("foo + 1", "23", "1")),
+        (3, 20, "`old` or an integer".to_string())
+    );
+    assert_eq!(
+        error(monkey("old * 19", "0", "1")),
+        (4, 22, "a positive divisor".to_string())
+    );
+    assert_eq!(
+        error(monkey("old * 19", "23", "2")),
+        (5, 30, "a monkey other than 0 between 0 and 1".to_string())
+    );
+    assert_eq!(error(monkey("old * 19", "23", "0")).0, 5);
+
+    // Ids count up from 0 in order.
+    assert_eq!(
+        error(monkey("old * 19", "23", "1").replace("Monkey 1:", "Monkey 2:")),
+        (8, 8, "monkey 1".to_string())
+    );
+    assert_eq!(
+        error(monkey("old * 19", "23", "1").replace("Monkey 0:", "Monkey 1:")).2,
+        "monkey 0"
+    );
+
+    let parsed = Day11::parse_input("").unwrap();
+    assert!(Day11::part1(&parsed).is_err());
+    assert!(Day11::part2(&parsed).is_err());
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{map, value},
+    error::context,
+    sequence::separated_pair,
+};
+
+use crate::{
+    parse::{IResult, Span},
+    solution::Solution,
+};
+
+type Input = Vec<Round>;
+
+#[derive(Clone)]
+enum Startegy {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Clone)]
+enum Play {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+pub struct Round {
+    me: Startegy,
+    opponent: Play,
+}
+
+impl Round {
+    fn score(&self) -> i32 {
+        let my_shape = match &self.me {
+            Startegy::X => Play::Rock,
+            Startegy::Y => Play::Paper,
+            Startegy::Z => Play::Scissors,
+        };
+
+        let shape = match &my_shape {
+            Play::Rock => 1,
+            Play::Paper => 2,
+            Play::Scissors => 3,
+        };
+
+        let results = match (&my_shape, &self.opponent) {
+            (Play::Rock, Play::Rock) => 3,
+            (Play::Rock, Play::Paper) => 0,
+            (Play::Rock, Play::Scissors) => 6,
+            (Play::Paper, Play::Rock) => 6,
+            (Play::Paper, Play::Paper) => 3,
+            (Play::Paper, Play::Scissors) => 0,
+            (Play::Scissors, Play::Rock) => 0,
+            (Play::Scissors, Play::Paper) => 6,
+            (Play::Scissors, Play::Scissors) => 3,
+        };
+
+        shape + results
+    }
+
+    fn p2_score(&self) -> i32 {
+        // X - Need to lose
+        // Y - Need to draw
+        // Z - Need to win
+        let my_shape = match (&self.me, &self.opponent) {
+            (Startegy::X, Play::Rock) => Play::Scissors,
+            (Startegy::X, Play::Paper) => Play::Rock,
+            (Startegy::X, Play::Scissors) => Play::Paper,
+            (Startegy::Y, Play::Rock) => Play::Rock,
+            (Startegy::Y, Play::Paper) => Play::Paper,
+            (Startegy::Y, Play::Scissors) => Play::Scissors,
+            (Startegy::Z, Play::Rock) => Play::Paper,
+            (Startegy::Z, Play::Paper) => Play::Scissors,
+            (Startegy::Z, Play::Scissors) => Play::Rock,
+        };
+
+        let shape = match &my_shape {
+            Play::Rock => 1,
+            Play::Paper => 2,
+            Play::Scissors => 3,
+        };
+
+        let score = match &self.me {
+            Startegy::X => 0,
+            Startegy::Y => 3,
+            Startegy::Z => 6,
+        };
+
+        shape + score
+    }
+}
+
+fn round(s: &str) -> IResult<'_, Round> {
+    let opponent = context(
+        "one of `A`, `B`, `C`",
+        alt((
+            value(Play::Rock, char('A')),
+            value(Play::Paper, char('B')),
+            value(Play::Scissors, char('C')),
+        )),
+    );
+
+    let me = context(
+        "one of `X`, `Y`, `Z`",
+        alt((
+            value(Startegy::X, char('X')),
+            value(Startegy::Y, char('Y')),
+            value(Startegy::Z, char('Z')),
+        )),
+    );
+
+    map(separated_pair(opponent, char(' '), me), |(opponent, me)| {
+        Round { me, opponent }
+    })(s)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Input;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        Ok(Span::new(input).parse_lines(round)?)
+    }
+
+    fn part1(input: &Input) -> Result<i32> {
+        Ok(input.iter().map(|i| i.score()).sum())
+    }
+
+    fn part2(input: &Input) -> Result<i32> {
+        Ok(input.iter().map(|i| i.p2_score()).sum())
+    }
+}
+
+#[test]
+fn test() {
+    let input = "A Y
+B X
+C Z
+";
+    let parsed = Day2::parse_input(input).unwrap();
+
+    assert_eq!(Day2::part1(&parsed).unwrap(), 15);
+    assert_eq!(Day2::part2(&parsed).unwrap(), 12);
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::{parse::ParseError, solution::Solution};
+
+type Input = Vec<Rucksack>;
+
+#[derive(Debug)]
+pub struct Rucksack(HashSet<char>, HashSet<char>);
+
+impl Rucksack {
+    fn common(&self) -> Vec<char> {
+        self.0.intersection(&self.1).cloned().collect_vec()
+    }
+
+    fn joined(&self) -> HashSet<char> {
+        self.0.union(&self.1).cloned().collect()
+    }
+}
+
+fn priority(c: char) -> i32 {
+    match c {
+        'a'..='z' => (c as u32) as i32 - 96,
+        'A'..='Z' => (c as u32) as i32 - 38,
+        _ => todo!(),
+    }
+}
+
+#[test]
+fn test_priority() {
+    assert_eq!(priority('a'), 1);
+    assert_eq!(priority('z'), 26);
+    assert_eq!(priority('A'), 27);
+    assert_eq!(priority('Z'), 52);
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Input;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        let mut rucksacks = vec![];
+
+        for line in input.lines() {
+            let line = line.trim();
+
+            if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                let item = &line[i..i + c.len_utf8()];
+                return Err(ParseError::at(input, item, "an item type in `a-z` or `A-Z`").into());
+            }
+
+            let n = line.len();
+            let half = n / 2;
+
+            let chars = line.chars().collect_vec();
+            let mut h1: HashSet<char> = HashSet::new();
+            let mut h2: HashSet<char> = HashSet::new();
+
+            h1.extend(&chars[..half]);
+            h2.extend(&chars[half..]);
+
+            rucksacks.push(Rucksack(h1, h2))
+        }
+
+        Ok(rucksacks)
+    }
+
+    fn part1(input: &Input) -> Result<i32> {
+        Ok(input
+            .iter()
+            .map(|sack| sack.common().iter().cloned().map(priority).sum::<i32>())
+            .sum())
+    }
+
+    fn part2(input: &Input) -> Result<i32> {
+        Ok(input
+            .chunks(3)
+            .filter(|chunk| !chunk.is_empty())
+            .map(|chunk| {
+                let mut common = chunk[0].joined();
+                for elf in chunk.iter().skip(1) {
+                    common = common.intersection(&elf.joined()).cloned().collect()
+                }
+
+                common.iter().cloned().map(priority).sum::<i32>()
+            })
+            .sum())
+    }
+}
+
+#[test]
+fn test() {
+    let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+    ";
+
+    let parsed = Day3::parse_input(input).unwrap();
+
+    assert_eq!(Day3::part1(&parsed).unwrap(), 157);
+    assert_eq!(Day3::part2(&parsed).unwrap(), 70);
+}
@@ -0,0 +1,68 @@
+use std::ops::RangeInclusive;
+
+use anyhow::Result;
+use nom::{character::complete::char, sequence::separated_pair};
+
+use crate::{
+    parse::{range, Span},
+    solution::Solution,
+};
+
+type Input = Vec<(RangeInclusive<i32>, RangeInclusive<i32>)>;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        Ok(Span::new(input).parse_lines(separated_pair(range, char(','), range))?)
+    }
+
+    /// For every pair of ranges, chech in how many ranges one fully contains the other
+    fn part1(input: &Input) -> Result<usize> {
+        Ok(input
+            .iter()
+            .filter(|(r1, r2)| {
+                ((r1.start() <= r2.start()) && (r1.end() >= r2.end()))
+                    || (r2.start() <= r1.start()) && (r2.end() >= r1.end())
+            })
+            .count())
+    }
+
+    /// For every pair of ranges, chech in how many ranges overlap at all.
+    fn part2(input: &Input) -> Result<usize> {
+        Ok(input
+            .iter()
+            // we can check if the absolute sum of the first start from the second end is less than the sum.
+            .filter(|(r1, r2)| {
+                let r1_span = r1.end().abs_diff(*r1.start()) as i32;
+                let r2_span = r2.end().abs_diff(*r2.start()) as i32;
+                let total_span = r1_span + r2_span;
+                let r1_r2_diff = r1.end().abs_diff(*r2.start()) as i32;
+                let r2_r1_diff = r2.end().abs_diff(*r1.start()) as i32;
+
+                (r1_r2_diff <= total_span) && (r2_r1_diff <= total_span)
+            })
+            .count())
+    }
+}
+
+#[test]
+fn test() {
+    let input = "
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    let parsed = Day4::parse_input(input).unwrap();
+    assert_eq!(Day4::part1(&parsed).unwrap(), 2);
+    assert_eq!(Day4::part2(&parsed).unwrap(), 4);
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{Display, Write},
+};
+
+use anyhow::{Context, Result};
+use log::debug;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, satisfy, space0},
+    combinator::{map, value},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+use crate::{
+    parse::{int, words, IResult, Span},
+    solution::Solution,
+};
+
+type Stack<T> = Vec<T>;
+type Input = CraneProblem;
+
+#[derive(Clone, Debug)]
+struct Instruction {
+    quantity: usize,
+    from: usize,
+    to: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct CraneProblem {
+    state: BTreeMap<usize, Stack<String>>,
+    operations: Vec<Instruction>,
+}
+
+impl Display for CraneProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut state = self.clone();
+        let mut tallest = state.state.values().map(|v| v.len()).max().unwrap();
+
+        while tallest > 0 {
+            for stack in state.state.values_mut() {
+                if stack.len() < tallest {
+                    f.write_str("   ")?;
+                } else {
+                    f.write_char('[')?;
+                    f.write_char(stack.pop().unwrap().chars().next().unwrap())?;
+                    f.write_char(']')?;
+                }
+                f.write_char(' ')?;
+            }
+            f.write_char('\n')?;
+
+            tallest -= 1;
+        }
+
+        for idx in state.state.keys() {
+            f.write_fmt(format_args!("{:3} ", idx))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Either a crate like `[A]`, or three spaces where there is none.
+fn crate_slot(s: &str) -> IResult<'_, Option<char>> {
+    context(
+        "a crate like `[A]` or an empty slot",
+        alt((
+            map(
+                delimited(char('['), satisfy(|c| c.is_alphabetic()), char(']')),
+                Some,
+            ),
+            value(None, tag("   ")),
+        )),
+    )(s)
+}
+
+fn instruction(s: &str) -> IResult<'_, Instruction> {
+    context(
+        "an instruction like `move 1 from 2 to 1`",
+        map(
+            tuple((
+                preceded(tag("move "), int),
+                preceded(tag(" from "), int),
+                preceded(tag(" to "), int),
+            )),
+            |(quantity, from, to)| Instruction { quantity, from, to },
+        ),
+    )(s)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Input;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        let input = Span::new(input);
+        let mut blocks = input.blocks();
+
+        let drawing = blocks
+            .next()
+            .ok_or_else(|| input.error_at_end("a drawing of the stacks"))?;
+        let mut rows = drawing.lines().collect::<Vec<_>>();
+
+        // The last row only numbers the stacks.
+        rows.pop()
+            .ok_or_else(|| drawing.error_at_end("a row of stack numbers"))?
+            .parse(words(int::<usize>))?;
+
+        // Parse the stacks bottom up.
+        let mut problem_state = BTreeMap::new();
+
+        for row in rows.iter().rev() {
+            let boxes = row.parse(terminated(separated_list1(char(' '), crate_slot), space0))?;
+
+            for (i, b) in boxes.into_iter().enumerate() {
+                if let Some(letter) = b {
+                    problem_state
+                        .entry(i + 1)
+                        .or_insert_with(Stack::new)
+                        .push(letter.to_string())
+                }
+            }
+        }
+
+        let problem_instructions = match blocks.next() {
+            Some(instructions) => instructions.parse_lines(instruction)?,
+            None => vec![],
+        };
+
+        let problem = CraneProblem {
+            state: problem_state,
+            operations: problem_instructions,
+        };
+        debug!("\n{}", problem);
+
+        Ok(problem)
+    }
+
+    fn part1(input: &Input) -> Result<String> {
+        let mut board = input.clone();
+
+        for instruction in board.operations {
+            for _ in 0..instruction.quantity {
+                let item = board
+                    .state
+                    .get_mut(&instruction.from)
+                    .context(format!("Expected a stack at {}", instruction.from))?
+                    .pop()
+                    .context(format!("Expected a box at {}", instruction.from))?;
+
+                board
+                    .state
+                    .get_mut(&instruction.to)
+                    .context(format!("Expected a stack at {}", instruction.from))?
+                    .push(item);
+            }
+        }
+
+        let mut result = String::new();
+
+        for letters in board.state.values() {
+            let letter = letters.last();
+
+            if let Some(l) = letter {
+                result.push_str(l);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn part2(input: &Input) -> Result<String> {
+        let mut board = input.clone();
+
+        for instruction in board.operations {
+            let mut buffer = VecDeque::new();
+
+            for _ in 0..instruction.quantity {
+                let item = board
+                    .state
+                    .get_mut(&instruction.from)
+                    .context(format!("Expected a stack at {}", instruction.from))?
+                    .pop()
+                    .context(format!("Expected a box at {}", instruction.from))?;
+
+                buffer.push_front(item);
+            }
+
+            board
+                .state
+                .get_mut(&instruction.to)
+                .context(format!("Expected a stack at {}", instruction.from))?
+                .extend(buffer.iter().cloned())
+        }
+
+        let mut result = String::new();
+
+        for letters in board.state.values() {
+            let letter = letters.last();
+
+            if let Some(l) = letter {
+                result.push_str(l);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[test]
+fn test() {
+    let input = "
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    let parsed = Day5::parse_input(input).unwrap();
+    println!("{}", parsed);
+
+    assert_eq!(Day5::part1(&parsed).unwrap(), "CMZ".to_string());
+    assert_eq!(Day5::part2(&parsed).unwrap(), "MCD".to_string());
+}
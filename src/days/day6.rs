@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+use crate::{parse::ParseError, solution::Solution};
+
+type Input = String;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        let signal = input.trim_end();
+
+        if let Some((i, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            let bad = &signal[i..i + c.len_utf8()];
+            return Err(ParseError::at(input, bad, "a lowercase letter").into());
+        }
+
+        Ok(signal.to_string())
+    }
+
+    fn part1(input: &Input) -> Result<usize> {
+        start_of_signal(input, 4).context("No start-of-packet marker")
+    }
+
+    fn part2(input: &Input) -> Result<usize> {
+        start_of_signal(input, 14).context("No start-of-message marker")
+    }
+}
+
+fn start_of_signal(s: &str, sz: usize) -> Option<usize> {
+    let chars = s.chars().collect_vec();
+
+    for (i, v) in chars.windows(sz).enumerate() {
+        let set: HashSet<&char> = HashSet::from_iter(v);
+        if set.len() == sz {
+            return Some(i + sz);
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        start_of_signal("bvwbjplbgvbhsrlpgdmjqwftvncz", 4).unwrap(),
+        5
+    );
+    assert_eq!(
+        start_of_signal("nppdvjthqldpwncqszvftbrmjlhg", 4).unwrap(),
+        6
+    );
+    assert_eq!(
+        start_of_signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4).unwrap(),
+        10
+    );
+    assert_eq!(
+        start_of_signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14).unwrap(),
+        19
+    );
+}
@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+use crate::{parse::ParseError, solution::Solution};
+
+#[derive(Debug)]
+enum Entry {
+    Dir,
+    File(usize),
+}
+
+type Input = FileSystem;
+
+#[derive(Debug)]
+pub struct FileSystem(BTreeMap<String, Entry>);
+
+impl FileSystem {
+    pub fn du(&self) -> HashMap<String, usize> {
+        let mut du = HashMap::new();
+
+        for (path, file_size) in self.0.iter() {
+            if let Entry::File(sz) = file_size {
+                *du.entry("/".to_string()).or_insert(0) += sz;
+
+                let dirs = path.split('/').collect_vec();
+
+                let n = dirs.len();
+
+                for i in 0..n {
+                    let d = dirs[0..i].join("/");
+
+                    if d.is_empty() {
+                        continue;
+                    }
+
+                    *du.entry(d).or_insert(0) += sz;
+                }
+            }
+        }
+        du
+    }
+}
+
+fn mkpath(stack: &[&str], f: Option<&str>) -> String {
+    let mut path = String::new();
+    path.push('/');
+    if !stack.is_empty() {
+        path.push_str(&stack.join("/"));
+        path.push('/');
+    }
+
+    if let Some(f) = f {
+        path.push_str(f);
+    }
+
+    path
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        let mut cwd = Vec::new();
+        let mut fs = BTreeMap::new();
+
+        for line in input.lines() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let words = line.split(' ').collect_vec();
+            let word = |i: usize, expected: &str| {
+                words
+                    .get(i)
+                    .copied()
+                    .ok_or_else(|| ParseError::at(input, &line[line.len()..], expected))
+            };
+
+            if line.starts_with('$') {
+                match word(1, "a shell command")? {
+                    "cd" => {
+                        let path = word(2, "a path")?;
+                        if path == ".." {
+                            cwd.pop();
+                        } else if path == "/" {
+                            continue;
+                        } else {
+                            cwd.push(path)
+                        }
+                    }
+                    "ls" => {
+                        continue;
+                    }
+                    command => {
+                        return Err(ParseError::at(input, command, "one of `cd`, `ls`").into())
+                    }
+                }
+            } else {
+                match words[0] {
+                    "dir" => {
+                        let path = mkpath(&cwd, Some(word(1, "a directory name")?));
+                        fs.insert(path, Entry::Dir);
+                    }
+                    sz => {
+                        let n = sz
+                            .parse::<usize>()
+                            .map_err(|_| ParseError::at(input, sz, "`dir` or a file size"))?;
+
+                        let path = mkpath(&cwd, Some(word(1, "a file name")?));
+
+                        fs.insert(path, Entry::File(n));
+                    }
+                }
+            }
+        }
+
+        Ok(FileSystem(fs))
+    }
+
+    fn part1(input: &Input) -> Result<usize> {
+        Ok(input.du().values().filter(|&&v| v <= 100000).sum())
+    }
+
+    fn part2(input: &Input) -> Result<usize> {
+        let du = input.du();
+
+        let total_space = 70000000;
+        let needed_space = 30000000;
+        let unused_space = total_space - du["/"];
+
+        Ok(*du
+            .values()
+            .sorted()
+            .find_or_first(|v| unused_space + *v >= needed_space)
+            .context("impossible to free space")?)
+    }
+}
+
+#[test]
+fn test() {
+    let input = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    let parsed = dbg!(Day7::parse_input(input).unwrap());
+
+    assert_eq!(Day7::part1(&parsed).unwrap(), 95437);
+    assert_eq!(Day7::part2(&parsed).unwrap(), 24933642);
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use log::trace;
+
+use crate::{
+    grid::{Grid, Point, ORTHOGONAL},
+    solution::Solution,
+};
+
+type Input = Grid<u8>;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        Ok(Grid::parse(input, |c| {
+            c.to_digit(10).map(|d| d as u8).ok_or("a digit")
+        })?)
+    }
+
+    fn part1(input: &Input) -> Result<usize> {
+        Ok(input.points().filter(|&p| is_visible(input, p)).count())
+    }
+
+    fn part2(input: &Input) -> Result<usize> {
+        Ok(input
+            .points()
+            .map(|p| scenic_score(input, p))
+            .max()
+            .unwrap_or(0))
+    }
+}
+
+fn is_visible(grid: &Grid<u8>, p: Point) -> bool {
+    let tree = grid[p];
+    trace!("{:?} - value {}", p, tree);
+
+    ORTHOGONAL
+        .iter()
+        .any(|&d| grid.ray(p, d).all(|(_, &v)| v < tree))
+}
+
+/// Number of trees seen along `line` from a tree of height `tree`, including the blocking tree.
+fn viewing_distance<'a>(tree: u8, line: impl Iterator<Item = &'a u8>) -> usize {
+    let mut seen = 0;
+
+    for v in line {
+        seen += 1;
+
+        if *v >= tree {
+            break;
+        }
+    }
+
+    seen
+}
+
+fn scenic_score(grid: &Grid<u8>, p: Point) -> usize {
+    let tree = grid[p];
+    trace!("{:?} - value {}", p, tree);
+
+    ORTHOGONAL
+        .iter()
+        .map(|&d| viewing_distance(tree, grid.ray(p, d).map(|(_, v)| v)))
+        .product()
+}
+
+#[test]
+fn test() {
+    let input = "
+30373
+25512
+65332
+33549
+35390";
+
+    let parsed = Day8::parse_input(input).unwrap();
+    assert!(!is_visible(&parsed, (1, 3).into()));
+
+    assert_eq!(Day8::part1(&parsed).unwrap(), 21);
+
+    assert_eq!(scenic_score(&parsed, (2, 3).into()), 8);
+
+    assert_eq!(Day8::part2(&parsed).unwrap(), 8);
+}
@@ -0,0 +1,309 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::Result;
+use log::{log_enabled, trace, Level};
+use nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{map, value},
+    error::context,
+    sequence::separated_pair,
+};
+
+use crate::{
+    grid::{Chunked, Direction, InfiniteGrid, Point, Rect, Storage},
+    image::{FrameRecorder, ImageOptions},
+    parse::{int, IResult, Span},
+    render::{render, RenderOptions},
+    solution::{Solution, Variant},
+};
+
+type Input = Vec<Instruction>;
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    direction: Direction,
+    steps: usize,
+}
+
+struct Rope {
+    inner: Vec<Point>,
+}
+
+impl Rope {
+    pub fn new(num_knots: usize) -> Self {
+        assert!(num_knots >= 2);
+        Rope {
+            inner: vec![(0, 0).into(); num_knots],
+        }
+    }
+
+    pub fn step_head(&mut self, direction: Direction) {
+        self.inner[0] += direction.offset();
+
+        for k in 1..self.inner.len() {
+            let knot = *self.inner.get(k).expect("checked len");
+            let prev = *self.inner.get(k - 1).expect("checked len");
+
+            self.inner[k] = Self::new_knot_position(knot, prev);
+        }
+    }
+
+    pub fn tail_position(&self) -> Point {
+        self.inner[self.inner.len() - 1]
+    }
+
+    /// Draws the positions visited by the tail, with the start as `s` and the knots on top.
+    fn render<S: Storage<bool>>(&self, visited: &InfiniteGrid<bool, S>) -> String {
+        let viewport = self
+            .inner
+            .iter()
+            .fold(visited.dimensions(), |bounds, &knot| match bounds {
+                Some(bounds) => Some(bounds.extend(knot)),
+                None => Some(Rect::point(knot)),
+            });
+
+        let options = RenderOptions {
+            viewport,
+            axes: true,
+            ..RenderOptions::default()
+        };
+
+        let mut overlays = vec![(Point::ZERO, 's')];
+        // Knots closer to the head are drawn over those behind them.
+        overlays.extend(self.inner.iter().enumerate().rev().map(|(i, &p)| {
+            (
+                p,
+                if i == 0 {
+                    'H'
+                } else {
+                    (b'0' + i as u8) as char
+                },
+            )
+        }));
+
+        let mut s = String::new();
+        render(
+            &mut s,
+            visited,
+            &options,
+            |_, v| if v.is_some() { '#' } else { '.' },
+            &overlays,
+        )
+        .expect("writing to a string");
+
+        s
+    }
+
+    /// Calculate new tail position given head position: a knot stays put while touching the one
+    /// ahead of it, and otherwise takes a single (possibly diagonal) step towards it.
+    fn new_knot_position<T: Into<Point>>(tail: T, head: T) -> Point {
+        let tail = tail.into();
+        let head = head.into();
+
+        if tail.chebyshev(head) > 1 {
+            tail.step_toward(head)
+        } else {
+            tail
+        }
+    }
+}
+
+fn instruction(s: &str) -> IResult<'_, Instruction> {
+    // `U` moves north, to smaller `y`, so the rope is drawn like a grid with its first row on top.
+    let direction = context(
+        "one of `R`, `L`, `U`, `D`",
+        alt((
+            value(Direction::E, char('R')),
+            value(Direction::W, char('L')),
+            value(Direction::N, char('U')),
+            value(Direction::S, char('D')),
+        )),
+    );
+
+    map(
+        separated_pair(direction, char(' '), context("a number of steps", int)),
+        |(direction, steps)| Instruction { direction, steps },
+    )(s)
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse_input(input: &str) -> Result<Input> {
+        Ok(Span::new(input).parse_lines(instruction)?)
+    }
+
+    fn part1(input: &Input) -> Result<usize> {
+        tail_visits::<Chunked<_>>(input, 2, |_, _| Ok(()))
+    }
+
+    fn part2(input: &Input) -> Result<usize> {
+        tail_visits::<Chunked<_>>(input, 10, |_, _| Ok(()))
+    }
+
+    /// The parts on the default [`HashMap`] storage, to compare it with [`Chunked`].
+    fn variants() -> Vec<Variant<Input>> {
+        vec![
+            Variant {
+                name: "part1 (HashMap)",
+                run: |input| Ok(tail_visits::<HashMap<_, _>>(input, 2, |_, _| Ok(()))?.into()),
+            },
+            Variant {
+                name: "part2 (HashMap)",
+                run: |input| Ok(tail_visits::<HashMap<_, _>>(input, 10, |_, _| Ok(()))?.into()),
+            },
+        ]
+    }
+}
+
+/// The number of positions the tail of a rope of `num_knots` visits, calling `on_step` after
+/// every step.
+fn tail_visits<S: Storage<bool>>(
+    input: &Input,
+    num_knots: usize,
+    mut on_step: impl FnMut(&Rope, &InfiniteGrid<bool, S>) -> Result<()>,
+) -> Result<usize> {
+    let mut state: InfiniteGrid<bool, S> = InfiniteGrid::default();
+    let mut rope = Rope::new(num_knots);
+
+    for instruction in input {
+        trace!("{:?} {}", instruction.direction, instruction.steps);
+        for _ in 0..instruction.steps {
+            rope.step_head(instruction.direction);
+            state.entry(rope.tail_position()).or_insert(true);
+            on_step(&rope, &state)?;
+        }
+        state.entry(rope.tail_position()).or_insert(true);
+
+        if log_enabled!(Level::Trace) {
+            trace!("\n{}", rope.render(&state));
+        }
+    }
+
+    Ok(state.num_points())
+}
+
+/// Records a frame per step of a rope of `num_knots` into `dir`, with `scale` pixels per cell.
+///
+/// Frames show the whole area the rope moves through, the visited positions in grey and the
+/// knots in red, with the head in white.
+pub fn record_frames(input: &Input, num_knots: usize, dir: &Path, scale: usize) -> Result<usize> {
+    let mut viewport = Rect::point(Point::ZERO);
+    tail_visits::<Chunked<_>>(input, num_knots, |rope, _| {
+        viewport = rope.inner.iter().fold(viewport, |r, &knot| r.extend(knot));
+        Ok(())
+    })?;
+
+    let options = ImageOptions {
+        viewport: Some(viewport),
+        scale,
+    };
+    let mut recorder = FrameRecorder::new(dir, options)?;
+
+    tail_visits::<Chunked<_>>(input, num_knots, |rope, state| {
+        recorder.record(state, |p, visited| {
+            match rope.inner.iter().position(|&k| k == p) {
+                Some(0) => [255, 255, 255],
+                Some(_) => [220, 40, 40],
+                None if visited.is_some() => [110, 110, 110],
+                None => [0, 0, 0],
+            }
+        })?;
+
+        Ok(())
+    })?;
+
+    Ok(recorder.frames())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case((0, 0), (2, 0) , (1, 0))]
+    #[case((0, 0), (-2, 0),(-1, 0))]
+    #[case((0, 0), (0, 2) ,(0, 1))]
+    #[case((0, 0), (0, -2),(0, -1))]
+    #[case((0, 0), (1, 2),(1, 1))]
+    #[case((0, 0), (-1, 2),(-1, 1))]
+    #[case((0, 0), (1, -2),(1, -1))]
+    #[case((0, 0), (-1, -2),(-1, -1))]
+    #[case((0, 0), (2, 1),(1, 1))]
+    fn test_tail_position(
+        #[case] head: (i32, i32),
+        #[case] tail: (i32, i32),
+        #[case] expected: (i32, i32),
+    ) {
+        assert_eq!(
+            Rope::new_knot_position::<(i32, i32)>(head, tail),
+            expected.into()
+        );
+    }
+}
+
+#[test]
+fn test_day9() {
+    let _ = env_logger::try_init();
+    let input = "
+R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    let parsed = Day9::parse_input(input).unwrap();
+    assert_eq!(Day9::part1(&parsed).unwrap(), 13);
+    assert_eq!(Day9::part2(&parsed).unwrap(), 1);
+
+    let larger_input = "
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+    let parsed = Day9::parse_input(larger_input).unwrap();
+    assert_eq!(Day9::part2(&parsed).unwrap(), 36);
+
+    use crate::answer::Answer;
+
+    let [part1, part2] = Day9::variants().try_into().ok().unwrap();
+    assert_eq!((part1.run)(&parsed).unwrap(), Answer::from(88));
+    assert_eq!((part2.run)(&parsed).unwrap(), Answer::from(36));
+}
+
+#[test]
+fn test_day9_parse_error() {
+    let e = Day9::parse_input("R 4\nX 3\n").unwrap_err();
+    let e = e.downcast::<crate::parse::ParseError>().unwrap();
+
+    assert_eq!((e.line, e.column), (2, 1));
+
+    let e = Day9::parse_input("R 4\nU four\n").unwrap_err();
+    let e = e.downcast::<crate::parse::ParseError>().unwrap();
+
+    assert_eq!((e.line, e.column, e.len), (2, 3, 4));
+}
+
+#[test]
+#[ignore = "writes a frame per step to `target/frames/day9`"]
+fn record_day9_frames() {
+    let input = Day9::parse_input("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20").unwrap();
+
+    let frames = record_frames(&input, 10, Path::new("target/frames/day9"), 8).unwrap();
+    assert_eq!(frames, 96);
+}
@@ -0,0 +1,105 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+
+use crate::solution::DynSolution;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Every solved day, ordered by day number.
+pub static ALL: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn DynSolution> {
+    ALL.iter().find(|s| s.day() == day).copied()
+}
+
+/// A selection of days, as given on the command line: `5`, `all`, `3..=7` or `3..7`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Range(RangeInclusive<u8>),
+}
+
+impl Selection {
+    pub fn solutions(&self) -> Result<Vec<&'static dyn DynSolution>> {
+        let solutions = match self {
+            Selection::All => ALL.to_vec(),
+            Selection::Range(range) => range
+                .clone()
+                .map(|day| get(day).context(format!("Day {} is not registered", day)))
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(solutions)
+    }
+}
+
+impl FromStr for Selection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |s: &str| {
+            s.trim()
+                .parse::<u8>()
+                .context(format!("Expected a day number, found `{}`", s))
+        };
+
+        if s == "all" {
+            return Ok(Selection::All);
+        }
+
+        let range = if let Some((start, end)) = s.split_once("..=") {
+            day(start)?..=day(end)?
+        } else if let Some((start, end)) = s.split_once("..") {
+            day(start)?..=day(end)?.saturating_sub(1)
+        } else {
+            let d = day(s)?;
+            d..=d
+        };
+
+        if range.is_empty() {
+            bail!("Empty range of days `{}`", s);
+        }
+
+        Ok(Selection::Range(range))
+    }
+}
+
+#[test]
+fn test_selection() {
+    assert_eq!("all".parse::<Selection>().unwrap(), Selection::All);
+    assert_eq!("5".parse::<Selection>().unwrap(), Selection::Range(5..=5));
+    assert_eq!(
+        "3..=7".parse::<Selection>().unwrap(),
+        Selection::Range(3..=7)
+    );
+    assert_eq!(
+        "3..7".parse::<Selection>().unwrap(),
+        Selection::Range(3..=6)
+    );
+    assert!("7..=3".parse::<Selection>().is_err());
+    assert!("x".parse::<Selection>().is_err());
+    assert!("20".parse::<Selection>().unwrap().solutions().is_err());
+}
@@ -1,77 +1,438 @@
 use std::{
-    collections::{hash_map, HashMap},
-    ops::Index,
+    cell::Cell,
+    collections::HashMap,
+    fmt::Display,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
 };
 
 use anyhow::bail;
 use itertools::Itertools;
 
-pub struct Grid {
-    inner: Vec<Vec<i32>>,
+use crate::parse::{ParseError, Span};
 
-    nrows: usize,
-    ncols: usize,
+mod grid3;
+mod storage;
+mod topology;
+mod view;
+
+pub use grid3::{
+    exterior, exterior_surface_area, surface_area, Cuboid, Entry3, Exterior, Grid3, InfiniteGrid3,
+    Point3, ADJACENT3, FACES,
+};
+pub use storage::{Chunked, Storage};
+pub use topology::{Bounded, CubeNet, Topology, Torus};
+pub use view::View;
+
+/// A dense, fixed size grid stored row-major, indexed by `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+
+    width: usize,
+    height: usize,
 }
 
-impl Grid {
-    pub fn new(inner: Vec<Vec<i32>>) -> anyhow::Result<Self> {
-        if !inner.iter().map(|v| v.len()).all_equal() {
+impl<T> Grid<T> {
+    pub fn new(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        if !rows.iter().map(|v| v.len()).all_equal() {
             bail!("Expected all rows to be of equal len");
         }
 
-        let nrows = inner.len();
-        let ncols = inner[0].len();
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// A grid of `width` columns over `cells`, given row by row.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> anyhow::Result<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            bail!(
+                "Expected a positive width dividing {} cells, got {}",
+                cells.len(),
+                width
+            );
+        }
+
         Ok(Self {
-            inner,
-            nrows,
-            ncols,
+            height: cells.len() / width,
+            cells,
+            width,
         })
     }
 
-    pub fn nrows(&self) -> usize {
-        self.nrows
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses a map of characters such as
+    ///
+    /// ```text
+    /// #.##
+    /// ..#.
+    /// ```
+    ///
+    /// mapping every character with `cell`. Blank lines before and after the map are skipped, and
+    /// all rows must be of the same length. Errors point at the offending line and column of
+    /// `input`, with the error of `cell` as what was expected.
+    pub fn parse<E: Display>(
+        input: &str,
+        cell: impl Fn(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        Span::new(input).char_grid(cell)
+    }
+
+    /// Renders the grid as text, one line per row.
+    pub fn render(&self, glyph: impl Fn(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            s.extend(row.iter().map(&glyph));
+            s.push('\n');
+        }
+
+        s
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, (x, y): (usize, usize)) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn contains(&self, idx: (usize, usize)) -> bool {
+        self.offset(idx).is_some()
+    }
+
+    pub fn get(&self, idx: (usize, usize)) -> Option<&T> {
+        self.offset(idx).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
+        self.offset(idx).map(|i| &mut self.cells[i])
+    }
+
+    fn point_offset(&self, p: Point) -> Option<usize> {
+        let x = usize::try_from(p.x).ok()?;
+        let y = usize::try_from(p.y).ok()?;
+
+        self.offset((x, y))
+    }
+
+    pub fn get_point(&self, p: Point) -> Option<&T> {
+        self.point_offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_point_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.point_offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position as a [`Point`], row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        self.positions()
+            .map(|(x, y)| Point::from((x as i32, y as i32)))
+    }
+
+    /// The up to 4 orthogonal neighbours of `p` within the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbours(p, &ORTHOGONAL, move |p| self.get_point(p))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbours of `p` within the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbours(p, &ADJACENT, move |p| self.get_point(p))
+    }
+
+    /// The cells from `from` (exclusive) in steps of `step` until the edge of the grid.
+    pub fn ray(&self, from: Point, step: Point) -> impl Iterator<Item = (Point, &T)> {
+        ray(from, step, move |p| self.get_point(p).map(Some))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn iter_row(&self, y: usize) -> std::slice::Iter<'_, T> {
+        self.row(y).iter()
+    }
+
+    /// The cells of column `x`, top to bottom. Can be reversed.
+    pub fn iter_col(
+        &self,
+        x: usize,
+    ) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        assert!(
+            x < self.width,
+            "column {} out of bounds of width {}",
+            x,
+            self.width
+        );
+
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
     }
 
-    pub fn ncols(&self) -> usize {
-        self.ncols
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
     }
+}
 
-    pub fn iter_row(&self, i: usize) -> impl Iterator<Item = i32> {
-        self.inner[i].clone().into_iter()
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
+
+    fn index(&self, y: usize) -> &Self::Output {
+        self.row(y)
     }
+}
 
-    pub fn iter_col(&self, i: usize) -> impl Iterator<Item = i32> + '_ {
-        let mut c = 0;
-        std::iter::from_fn(move || {
-            let r = if c < self.nrows() {
-                Some(self[(i, c)])
-            } else {
-                None
-            };
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, idx: (usize, usize)) -> &Self::Output {
+        let i = self.offset(idx).unwrap_or_else(|| {
+            panic!(
+                "{:?} out of bounds of a {}x{} grid",
+                idx, self.width, self.height
+            )
+        });
+
+        &self.cells[i]
+    }
+}
 
-            c += 1;
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
 
-            r
+    fn index(&self, p: Point) -> &Self::Output {
+        self.get_point(p).unwrap_or_else(|| {
+            panic!(
+                "{:?} out of bounds of a {}x{} grid",
+                p, self.width, self.height
+            )
         })
     }
 }
 
-impl Index<usize> for Grid {
-    type Output = Vec<i32>;
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, idx: (usize, usize)) -> &mut Self::Output {
+        let i = self.offset(idx).unwrap_or_else(|| {
+            panic!(
+                "{:?} out of bounds of a {}x{} grid",
+                idx, self.width, self.height
+            )
+        });
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.inner.index(index)
+        &mut self.cells[i]
     }
 }
 
-impl Index<(usize, usize)> for Grid {
-    type Output = i32;
+/// Offsets to the 4 orthogonal neighbours of a point, clockwise from [`Direction::N`].
+pub const ORTHOGONAL: [Point; 4] = [
+    Direction::N.offset(),
+    Direction::E.offset(),
+    Direction::S.offset(),
+    Direction::W.offset(),
+];
 
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        self.inner.index(index.1).index(index.0)
+/// Offsets to the 8 orthogonal and diagonal neighbours of a point, clockwise from
+/// [`Direction::N`].
+pub const ADJACENT: [Point; 8] = [
+    Direction::N.offset(),
+    Direction::NE.offset(),
+    Direction::E.offset(),
+    Direction::SE.offset(),
+    Direction::S.offset(),
+    Direction::SW.offset(),
+    Direction::W.offset(),
+    Direction::NW.offset(),
+];
+
+/// A compass direction. `y` grows to the south, like the rows of a [`Grid`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// All 8 directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// The step taken when moving one cell in this direction. North is towards smaller `y`, like
+    /// the first rows of a [`Grid`].
+    pub const fn offset(self) -> Point {
+        let (x, y) = match self {
+            Direction::N => (0, -1),
+            Direction::NE => (1, -1),
+            Direction::E => (1, 0),
+            Direction::SE => (1, 1),
+            Direction::S => (0, 1),
+            Direction::SW => (-1, 1),
+            Direction::W => (-1, 0),
+            Direction::NW => (-1, -1),
+        };
+
+        Point::new(x, y)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::ORTHOGONAL.contains(&self)
+    }
+
+    /// Rotates clockwise by `eighths` of a full turn, counter-clockwise when negative.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        let i = Self::ALL
+            .iter()
+            .position(|&d| d == self)
+            .expect("all directions") as i32;
+
+        Self::ALL[(i + eighths).rem_euclid(8) as usize]
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
     }
 }
 
+/// The positions at `offsets` from `p` for which `get` returns a cell, in 2D or 3D.
+fn neighbours<'a, P, T: 'a>(
+    p: P,
+    offsets: &'static [P],
+    get: impl Fn(P) -> Option<&'a T> + 'a,
+) -> impl Iterator<Item = (P, &'a T)> + 'a
+where
+    P: Copy + Add<Output = P> + 'a,
+{
+    offsets.iter().filter_map(move |d| {
+        let n = p + *d;
+        get(n).map(|t| (n, t))
+    })
+}
+
+/// Walks from `from` in steps of `step` while `get` returns `Some`, yielding the occupied cells.
+fn ray<'a, T: 'a>(
+    from: Point,
+    step: Point,
+    get: impl Fn(Point) -> Option<Option<&'a T>> + 'a,
+) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+    assert!(step != Point::ZERO, "a ray needs a non-zero step");
+
+    let mut p = from;
+
+    std::iter::from_fn(move || loop {
+        p += step;
+
+        match get(p)? {
+            Some(t) => return Some((p, t)),
+            None => continue,
+        }
+    })
+}
+
+/// The first `k >= 1` for which `from + step * k` is within `bounds`, if any.
+fn entry_step(bounds: Rect, from: Point, step: Point) -> Option<i32> {
+    let axes = [
+        (from.x, step.x, bounds.min.x, bounds.max.x),
+        (from.y, step.y, bounds.min.y, bounds.max.y),
+    ];
+    let (mut first, mut last) = (1, i64::MAX);
+
+    for (from, step, min, max) in axes {
+        let (from, step, min, max) = (from as i64, step as i64, min as i64, max as i64);
+
+        if step == 0 {
+            if !(min..=max).contains(&from) {
+                return None;
+            }
+            continue;
+        }
+
+        // Walk forward along the axis, mirroring it for negative steps.
+        let (from, step, min, max) = if step > 0 {
+            (from, step, min, max)
+        } else {
+            (-from, -step, -max, -min)
+        };
+
+        first = first.max(-(from - min).div_euclid(step));
+        last = last.min((max - from).div_euclid(step));
+    }
+
+    (first <= last).then_some(first as i32)
+}
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub struct Point {
     pub x: i32,
@@ -84,86 +445,654 @@ impl From<(i32, i32)> for Point {
     }
 }
 
-pub struct InfiniteGrid<T> {
-    inner: HashMap<Point, T>,
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// An axis aligned box of points, including both corners.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The box spanned by two opposite corners, in any order.
+    pub fn new(a: Point, b: Point) -> Self {
+        Rect {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The box holding only `p`.
+    pub fn point(p: Point) -> Self {
+        Rect { min: p, max: p }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// The points in both boxes, if any.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+        (min.x <= max.x && min.y <= max.y).then_some(Rect { min, max })
+    }
+
+    /// The smallest box holding both boxes.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::new(self.min, other.min)
+            .extend(self.max)
+            .extend(other.max)
+    }
+
+    /// The smallest box holding this box and `p`.
+    pub fn extend(&self, p: Point) -> Rect {
+        Rect {
+            min: Point::new(self.min.x.min(p.x), self.min.y.min(p.y)),
+            max: Point::new(self.max.x.max(p.x), self.max.y.max(p.y)),
+        }
+    }
+
+    /// All points in the box, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Rect { min, max } = *self;
+
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// A sparse grid, unbounded in every direction, keeping its cells in `S`.
+pub struct InfiniteGrid<T, S = HashMap<Point, T>> {
+    inner: S,
+    /// The bounding box of all points in `inner`, `None` when empty. Too large while `stale`.
+    bounds: Cell<Option<Rect>>,
+    /// Whether a point on the edge of `bounds` was removed since they were computed.
+    stale: Cell<bool>,
+    cell: PhantomData<T>,
 }
 
 impl<T> InfiniteGrid<T> {
     pub fn new() -> Self {
-        Self {
-            inner: HashMap::default(),
+        Self::default()
+    }
+}
+
+impl<T, S: Storage<T>> InfiniteGrid<T, S> {
+    /// The entry of `k`, for inserting or updating it.
+    pub fn entry(&mut self, k: Point) -> Entry<'_, T, S> {
+        Entry { grid: self, key: k }
+    }
+
+    pub fn insert(&mut self, k: Point, v: T) -> Option<T> {
+        self.extend_bounds(k);
+        self.inner.insert(k, v)
+    }
+
+    /// Removes `k`. The bounds shrink the next time they are needed if it was on their edge.
+    pub fn remove(&mut self, k: Point) -> Option<T> {
+        let removed = self.inner.remove(k)?;
+
+        if let Some(bounds) = self.bounds.get() {
+            let on_edge = k.x == bounds.min.x
+                || k.x == bounds.max.x
+                || k.y == bounds.min.y
+                || k.y == bounds.max.y;
+
+            if on_edge {
+                self.stale.set(true);
+            }
         }
+
+        Some(removed)
     }
 
-    pub fn entry(&mut self, k: Point) -> hash_map::Entry<'_, Point, T> {
-        self.inner.entry(k)
+    /// Removes every point, keeping the allocated storage for reuse.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.bounds.set(None);
+        self.stale.set(false);
+    }
+
+    fn extend_bounds(&mut self, k: Point) {
+        self.bounds.set(Some(match self.bounds.get() {
+            Some(bounds) => bounds.extend(k),
+            None => Rect::point(k),
+        }));
     }
 
     pub fn get(&self, k: Point) -> Option<&T> {
-        self.inner.get(&k)
+        self.inner.get(k)
     }
 
-    pub fn num_points(&mut self) -> usize {
-        self.inner.len()
+    pub fn get_mut(&mut self, k: Point) -> Option<&mut T> {
+        self.inner.get_mut(k)
+    }
+
+    /// The occupied points, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.inner.iter()
     }
 
-    pub fn dimensions(&self) -> (i32, i32, i32, i32) {
-        let min_x = self
-            .inner
-            .keys()
-            .min_by(|p1, p2| p1.x.cmp(&p2.x))
-            .unwrap()
-            .x;
+    /// The occupied orthogonal neighbours of `p`.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbours(p, &ORTHOGONAL, move |p| self.get(p))
+    }
 
-        let max_x = self
-            .inner
-            .keys()
-            .max_by(|p1, p2| p1.x.cmp(&p2.x))
-            .unwrap()
-            .x;
+    /// The occupied orthogonal and diagonal neighbours of `p`.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbours(p, &ADJACENT, move |p| self.get(p))
+    }
 
-        let min_y = self
-            .inner
-            .keys()
-            .min_by(|p1, p2| p1.y.cmp(&p2.y))
-            .unwrap()
-            .y;
+    /// The occupied cells from `from` (exclusive) in steps of `step`, until leaving the bounding
+    /// box of all occupied cells. A ray starting outside of the box skips ahead to where it enters.
+    pub fn ray(&self, from: Point, step: Point) -> impl Iterator<Item = (Point, &T)> {
+        let bounds = self.dimensions();
+        // The point just before the ray enters the bounds, `None` if it never does.
+        let start = bounds
+            .and_then(|bounds| entry_step(bounds, from, step))
+            .map(|k| from + step * (k - 1));
+
+        ray(start.unwrap_or(from), step, move |p| {
+            start?;
+            bounds?.contains(p).then(|| self.get(p))
+        })
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.inner.len()
+    }
 
-        let max_y = self
-            .inner
-            .keys()
-            .max_by(|p1, p2| p1.y.cmp(&p2.y))
-            .unwrap()
-            .y;
+    /// The bounding box of all points, `None` when the grid is empty.
+    ///
+    /// Recomputed from all points after a point on the edge was removed.
+    pub fn dimensions(&self) -> Option<Rect> {
+        if self.stale.replace(false) {
+            self.bounds.set(
+                self.inner
+                    .iter()
+                    .map(|(p, _)| Rect::point(p))
+                    .reduce(|bounds, p| bounds.union(&p)),
+            );
+        }
 
-        (min_x, max_x, min_y, max_y)
+        self.bounds.get()
     }
 }
 
-impl<T> Default for InfiniteGrid<T> {
+impl<T, S: Default> Default for InfiniteGrid<T, S> {
     fn default() -> Self {
-        Self::new()
+        Self {
+            inner: S::default(),
+            bounds: Cell::new(None),
+            stale: Cell::new(false),
+            cell: PhantomData,
+        }
+    }
+}
+
+/// A point of an [`InfiniteGrid`], for inserting or updating its value.
+pub struct Entry<'a, T, S> {
+    grid: &'a mut InfiniteGrid<T, S>,
+    key: Point,
+}
+
+impl<'a, T, S: Storage<T>> Entry<'a, T, S> {
+    pub fn key(&self) -> Point {
+        self.key
+    }
+
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'a mut T {
+        self.grid.extend_bounds(self.key);
+        self.grid.inner.get_or_insert_with(self.key, f)
+    }
+
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Updates the value with `f` if there is one.
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(v) = self.grid.inner.get_mut(self.key) {
+            f(v);
+        }
+
+        self
+    }
+}
+
+/// A position in a grid, with the box spanning positions.
+pub trait Position: Copy {
+    type Bounds;
+}
+
+impl Position for Point {
+    type Bounds = Rect;
+}
+
+impl Position for Point3 {
+    type Bounds = Cuboid;
+}
+
+/// Grids whose cells can be looked up by position, in two ([`Point`]) or three ([`Point3`])
+/// dimensions, for searching them, finding regions or drawing them.
+pub trait Cells<T, P: Position = Point> {
+    fn cell(&self, p: P) -> Option<&T>;
+
+    /// The box holding all cells, `None` when there are none.
+    fn bounds(&self) -> Option<P::Bounds>;
+
+    /// The stored cells, in no particular order.
+    fn cells(&self) -> Box<dyn Iterator<Item = (P, &T)> + '_>;
+}
+
+impl<T> Cells<T> for Grid<T> {
+    fn cell(&self, p: Point) -> Option<&T> {
+        self.get_point(p)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        (self.width() > 0 && self.height() > 0).then(|| {
+            Rect::new(
+                Point::ZERO,
+                Point::new(self.width() as i32 - 1, self.height() as i32 - 1),
+            )
+        })
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|((x, y), v)| (Point::new(x as i32, y as i32), v)),
+        )
+    }
+}
+
+impl<T, S: Storage<T>> Cells<T> for InfiniteGrid<T, S> {
+    fn cell(&self, p: Point) -> Option<&T> {
+        self.get(p)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        self.dimensions()
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        self.inner.iter()
     }
 }
 
 impl Point {
-    pub fn left(&self, x: i32) -> Point {
-        debug_assert!(x > 0, "left should be a positive amount");
-        (self.x - x, self.y).into()
+    pub const ZERO: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    /// Distance when moving only orthogonally.
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Distance when diagonal moves are allowed, i.e. the number of king moves.
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// The sign of each coordinate, a unit step in the direction of `self`.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Clamps each coordinate between those of `min` and `max`.
+    pub fn clamp(self, min: Point, max: Point) -> Point {
+        Point::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y))
+    }
+
+    /// One step (orthogonal or diagonal) from `self` towards `target`.
+    pub fn step_toward(self, target: Point) -> Point {
+        self + (target - self).clamp(Point::new(-1, -1), Point::new(1, 1))
     }
+}
+
+#[test]
+fn test_grid() {
+    let mut grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 0)], 3);
+    assert_eq!(grid[1], [4, 5, 6]);
+    assert_eq!(grid.get((0, 1)), Some(&4));
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.get((0, 2)), None);
+
+    assert_eq!(grid.iter_row(1).rev().collect_vec(), [&6, &5, &4]);
+    assert_eq!(grid.iter_col(1).copied().collect_vec(), [2, 5]);
+    assert_eq!(grid.iter_col(2).rev().copied().collect_vec(), [6, 3]);
+    assert_eq!(grid.iter_col(0).len(), 2);
+
+    grid[(1, 1)] = 50;
+    *grid.get_mut((2, 1)).unwrap() += 60;
+    assert!(grid.get_mut((1, 5)).is_none());
+    assert_eq!(grid.row(1), [4, 50, 66]);
 
-    pub fn right(&self, x: i32) -> Point {
-        debug_assert!(x > 0, "right should be a positive amount");
-        (self.x + x, self.y).into()
+    assert_eq!(
+        grid.iter()
+            .filter(|(_, &v)| v > 3)
+            .map(|(p, _)| p)
+            .collect_vec(),
+        [(0, 1), (1, 1), (2, 1)]
+    );
+
+    assert!(Grid::new(vec![vec![1], vec![2, 3]]).is_err());
+    assert!(Grid::from_vec(4, vec![0; 6]).is_err());
+}
+
+#[test]
+fn test_grid_cell_types() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall,
     }
 
-    pub fn up(&self, y: i32) -> Point {
-        debug_assert!(y > 0, "up should be a positive amount");
-        (self.x, self.y + y).into()
+    let chars = Grid::from_vec(2, "#..#".chars().collect()).unwrap();
+    let walls: Grid<bool> = chars.map(|&c| c == '#');
+    let mut tiles = walls.map(|&wall| if wall { Tile::Wall } else { Tile::Open });
+
+    assert_eq!(chars[(1, 1)], '#');
+    assert!(walls[(0, 0)] && !walls[(1, 0)]);
+    assert_eq!(
+        tiles.iter_col(0).copied().collect_vec(),
+        [Tile::Wall, Tile::Open]
+    );
+
+    tiles[(1, 0)] = Tile::Wall;
+    assert_eq!(tiles.row(0), [Tile::Wall, Tile::Wall]);
+
+    let empty = Grid::<u8>::filled(0, 0, 0);
+    assert_eq!(empty.rows().count(), 0);
+    assert_eq!(empty.get((0, 0)), None);
+}
+
+#[test]
+fn test_grid_parse_render() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall,
     }
 
-    pub fn down(&self, y: i32) -> Point {
-        debug_assert!(y > 0, "down should be a positive amount");
-        (self.x, self.y - y).into()
+    let map = "
+#..#
+.##.
+";
+
+    let grid = Grid::parse(map, |c| match c {
+        '.' => Ok(Tile::Open),
+        '#' => Ok(Tile::Wall),
+        _ => Err("one of `.`, `#`"),
+    })
+    .unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (4, 2));
+    assert_eq!(grid[(1, 1)], Tile::Wall);
+
+    let rendered = grid.render(|&t| if t == Tile::Wall { '#' } else { '.' });
+    assert_eq!(rendered, map.trim_start());
+
+    let e = Grid::parse("#..#\n.#x.\n", |c| match c {
+        '.' | '#' => Ok(c),
+        _ => Err(format!("`.` or `#`, found `{}`", c)),
+    })
+    .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "line 2, column 3: expected `.` or `#`, found `x`\n  |\n2 | .#x.\n  |   ^"
+    );
+}
+
+#[test]
+fn test_grid_neighbours() {
+    let grid = Grid::parse("abc\ndef\nghi\n", Ok::<_, &str>).unwrap();
+    let p = |x, y| Point::from((x, y));
+    let cells =
+        |it: &mut dyn Iterator<Item = (Point, &char)>| it.map(|(_, &c)| c).collect::<String>();
+
+    assert_eq!(cells(&mut grid.neighbours4(p(1, 1))), "bfhd");
+    assert_eq!(cells(&mut grid.neighbours8(p(1, 1))), "bcfihgda");
+    assert_eq!(cells(&mut grid.neighbours4(p(0, 0))), "bd");
+    assert_eq!(cells(&mut grid.neighbours8(p(2, 2))), "fhe");
+
+    assert_eq!(cells(&mut grid.ray(p(0, 0), p(1, 1))), "ei");
+    assert_eq!(cells(&mut grid.ray(p(2, 1), p(-1, 0))), "ed");
+    assert_eq!(cells(&mut grid.ray(p(1, 0), p(0, -1))), "");
+    assert_eq!(
+        grid.ray(p(0, 2), p(1, -1)).map(|(p, _)| p).collect_vec(),
+        [p(1, 1), p(2, 0)]
+    );
+    assert_eq!(grid[p(2, 1)], 'f');
+    assert_eq!(grid.get_point(p(-1, 0)), None);
+}
+
+#[test]
+fn test_infinite_grid_neighbours() {
+    let mut grid = InfiniteGrid::new();
+    let p = |x, y| Point::from((x, y));
+
+    assert_eq!(grid.ray(p(0, 0), p(1, 0)).count(), 0);
+
+    for (x, y) in [(0, 0), (1, 0), (3, 0), (1, 1), (-2, -2)] {
+        grid.entry(p(x, y)).or_insert(x * 10 + y);
+    }
+
+    assert_eq!(
+        grid.neighbours4(p(1, 0)).map(|(_, &v)| v).collect_vec(),
+        [11, 0]
+    );
+    assert_eq!(grid.neighbours8(p(0, 1)).count(), 3);
+
+    // Gaps are skipped, and the ray stops at the bounding box.
+    assert_eq!(
+        grid.ray(p(-2, 0), p(1, 0)).map(|(p, _)| p).collect_vec(),
+        [p(0, 0), p(1, 0), p(3, 0)]
+    );
+    assert_eq!(grid.ray(p(0, 0), p(-1, -1)).count(), 1);
+
+    // Rays from outside of the bounding box skip ahead to where they enter it.
+    assert_eq!(
+        grid.ray(p(-10, 0), p(1, 0)).map(|(p, _)| p).collect_vec(),
+        [p(0, 0), p(1, 0), p(3, 0)]
+    );
+    assert_eq!(
+        grid.ray(p(5, -5), p(-1, 1)).map(|(p, _)| p).collect_vec(),
+        [p(0, 0)]
+    );
+    assert_eq!(
+        grid.ray(p(-11, 1), p(2, 0)).map(|(p, _)| p).collect_vec(),
+        [p(1, 1)]
+    );
+    assert_eq!(grid.ray(p(10, 0), p(1, 0)).count(), 0);
+    assert_eq!(grid.ray(p(-10, 5), p(1, 0)).count(), 0);
+    assert_eq!(grid.ray(p(-10, -10), p(1, 3)).count(), 0);
+}
+
+#[test]
+fn test_infinite_grid_dimensions() {
+    let mut grid = InfiniteGrid::new();
+    assert_eq!(grid.dimensions(), None);
+
+    grid.insert(Point::new(2, -1), 'a');
+    grid.entry(Point::new(-3, 4)).or_insert('b');
+    grid.insert(Point::new(0, 0), 'c');
+
+    assert_eq!(
+        grid.dimensions(),
+        Some(Rect::new(Point::new(-3, -1), Point::new(2, 4)))
+    );
+    assert_eq!(grid.num_points(), 3);
+
+    assert_eq!(grid.remove(Point::new(0, 0)), Some('c'));
+    assert_eq!(grid.remove(Point::new(0, 0)), None);
+    assert_eq!(grid.remove(Point::new(-3, 4)), Some('b'));
+    assert_eq!(grid.dimensions(), Some(Rect::point(Point::new(2, -1))));
+
+    grid.remove(Point::new(2, -1));
+    assert_eq!(grid.dimensions(), None);
+
+    // Bounds shrink once the last point on an edge is gone.
+    for (x, y) in [(0, 0), (3, 0), (3, 2), (1, 1)] {
+        grid.insert(Point::new(x, y), 'a');
+    }
+    grid.insert(Point::new(3, 0), 'b');
+    grid.remove(Point::new(3, 0));
+    assert_eq!(
+        grid.dimensions(),
+        Some(Rect::new(Point::new(0, 0), Point::new(3, 2)))
+    );
+    grid.remove(Point::new(3, 2));
+    assert_eq!(
+        grid.dimensions(),
+        Some(Rect::new(Point::new(0, 0), Point::new(1, 1)))
+    );
+
+    grid.clear();
+    assert_eq!((grid.num_points(), grid.dimensions()), (0, None));
+}
+
+#[test]
+fn test_infinite_grid_entry() {
+    let mut grid: InfiniteGrid<u32, Chunked<u32>> = InfiniteGrid::default();
+
+    *grid.entry(Point::new(-20, 3)).or_default() += 1;
+    *grid.entry(Point::new(-20, 3)).or_default() += 1;
+    grid.entry(Point::new(5, 5)).and_modify(|v| *v = 9);
+    grid.entry(Point::new(0, 0))
+        .and_modify(|v| *v = 9)
+        .or_insert(1);
+
+    assert_eq!(grid.get(Point::new(-20, 3)), Some(&2));
+    assert_eq!(grid.get(Point::new(0, 0)), Some(&1));
+    assert_eq!(grid.num_points(), 2);
+    // Entries which are not inserted leave the bounds alone.
+    assert_eq!(
+        grid.dimensions(),
+        Some(Rect::new(Point::new(-20, 0), Point::new(0, 3)))
+    );
+}
+
+#[test]
+fn test_rect() {
+    let a = Rect::new(Point::new(2, 3), Point::new(0, 1));
+    let b = Rect::new(Point::new(1, 2), Point::new(4, 5));
+
+    assert_eq!((a.min, a.max), (Point::new(0, 1), Point::new(2, 3)));
+    assert_eq!((a.width(), a.height()), (3, 3));
+    assert!(a.contains(Point::new(2, 1)));
+    assert!(!a.contains(Point::new(3, 1)));
+
+    assert_eq!(
+        a.intersect(&b),
+        Some(Rect::new(Point::new(1, 2), Point::new(2, 3)))
+    );
+    assert_eq!(a.intersect(&Rect::point(Point::new(5, 5))), None);
+    assert_eq!(a.union(&b), Rect::new(Point::new(0, 1), Point::new(4, 5)));
+
+    assert_eq!(
+        Rect::new(Point::new(0, 0), Point::new(1, 1))
+            .points()
+            .collect_vec(),
+        [(0, 0), (1, 0), (0, 1), (1, 1)].map(Point::from)
+    );
+}
+
+#[test]
+fn test_point_arithmetic() {
+    let a = Point::new(3, -2);
+    let b = Point::new(-1, 4);
+
+    assert_eq!(a + b, Point::new(2, 2));
+    assert_eq!(a - b, Point::new(4, -6));
+    assert_eq!(-a, Point::new(-3, 2));
+    assert_eq!(a * 3, Point::new(9, -6));
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!(a.chebyshev(b), 6);
+    assert_eq!((a - b).signum(), Point::new(1, -1));
+    assert_eq!(a.clamp(Point::ZERO, Point::new(2, 2)), Point::new(2, 0));
+    assert_eq!(a.step_toward(b), Point::new(2, -1));
+    assert_eq!(a.step_toward(a), a);
+}
+
+#[test]
+fn test_direction() {
+    assert_eq!(Direction::N.turn_right(), Direction::E);
+    assert_eq!(Direction::N.turn_left(), Direction::W);
+    assert_eq!(Direction::NE.reverse(), Direction::SW);
+    assert_eq!(Direction::W.rotate(1), Direction::NW);
+    assert_eq!(Direction::N.rotate(-9), Direction::NW);
+    assert!(Direction::SE.is_diagonal());
+    assert!(!Direction::S.is_diagonal());
+
+    for d in Direction::ALL {
+        assert_eq!(d.offset() + d.reverse().offset(), Point::ZERO);
+        assert_eq!(d.turn_right().turn_left(), d);
     }
 }
@@ -0,0 +1,735 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use super::{neighbours, Cells};
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl From<(i32, i32, i32)> for Point3 {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl Point3 {
+    pub const ZERO: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// Distance when moving only along the axes.
+    pub fn manhattan(self, other: Point3) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Distance when diagonal moves are allowed.
+    pub fn chebyshev(self, other: Point3) -> i32 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    /// The sign of each coordinate, a unit step in the direction of `self`.
+    pub fn signum(self) -> Point3 {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// Clamps each coordinate between those of `min` and `max`.
+    pub fn clamp(self, min: Point3, max: Point3) -> Point3 {
+        Point3::new(
+            self.x.clamp(min.x, max.x),
+            self.y.clamp(min.y, max.y),
+            self.z.clamp(min.z, max.z),
+        )
+    }
+
+    /// One step (along the axes or diagonal) from `self` towards `target`.
+    pub fn step_toward(self, target: Point3) -> Point3 {
+        self + (target - self).signum()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Point3) -> Point3 {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Point3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Point3) -> Point3 {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Point3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i32> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, rhs: i32) -> Point3 {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// Offsets to the 6 neighbours of a point sharing a face with it.
+pub const FACES: [Point3; 6] = [
+    Point3::new(1, 0, 0),
+    Point3::new(-1, 0, 0),
+    Point3::new(0, 1, 0),
+    Point3::new(0, -1, 0),
+    Point3::new(0, 0, 1),
+    Point3::new(0, 0, -1),
+];
+
+/// Offsets to the 26 neighbours of a point sharing a face, edge or corner with it.
+pub const ADJACENT3: [Point3; 26] = {
+    let mut offsets = [Point3::ZERO; 26];
+    let mut i = 0;
+    let mut n = 0;
+
+    // Every offset in -1..=1 on each axis but the zero one.
+    while n < 27 {
+        if n != 13 {
+            offsets[i] = Point3::new(n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+            i += 1;
+        }
+        n += 1;
+    }
+
+    offsets
+};
+
+/// An axis aligned box of points, including both corners.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Cuboid {
+    /// The box spanned by two opposite corners, in any order.
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Cuboid {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// The box holding only `p`.
+    pub fn point(p: Point3) -> Self {
+        Cuboid { min: p, max: p }
+    }
+
+    /// Width, height and depth.
+    pub fn size(&self) -> (usize, usize, usize) {
+        let d = self.max - self.min;
+
+        (d.x as usize + 1, d.y as usize + 1, d.z as usize + 1)
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// The smallest box holding this box and `p`.
+    pub fn extend(&self, p: Point3) -> Cuboid {
+        Cuboid {
+            min: Point3::new(
+                self.min.x.min(p.x),
+                self.min.y.min(p.y),
+                self.min.z.min(p.z),
+            ),
+            max: Point3::new(
+                self.max.x.max(p.x),
+                self.max.y.max(p.y),
+                self.max.z.max(p.z),
+            ),
+        }
+    }
+
+    /// The box grown by `by` on every side.
+    pub fn grow(&self, by: i32) -> Cuboid {
+        let by = Point3::new(by, by, by);
+
+        Cuboid::new(self.min - by, self.max + by)
+    }
+
+    /// All points in the box, `x` fastest, then `y`, then `z`.
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let Cuboid { min, max } = *self;
+
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+}
+
+/// A dense, fixed size grid of voxels from `(0, 0, 0)`, stored with `x` fastest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+
+    width: usize,
+    height: usize,
+    depth: usize,
+}
+
+impl<T> Grid3<T> {
+    pub fn filled(width: usize, height: usize, depth: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height * depth],
+            width,
+            height,
+            depth,
+        }
+    }
+
+    /// Width, height and depth.
+    pub fn size(&self) -> (usize, usize, usize) {
+        (self.width, self.height, self.depth)
+    }
+
+    fn offset(&self, p: Point3) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height)?;
+        let z = usize::try_from(p.z).ok().filter(|&z| z < self.depth)?;
+
+        Some((z * self.height + y) * self.width + x)
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        self.offset(p).is_some()
+    }
+
+    pub fn get(&self, p: Point3) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point3) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, `x` fastest, then `y`, then `z`.
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let (width, height, depth) = self.size();
+
+        (0..depth as i32).flat_map(move |z| {
+            (0..height as i32)
+                .flat_map(move |y| (0..width as i32).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+
+    /// Every cell with its position, in the order of [`Grid3::points`].
+    pub fn iter(&self) -> impl Iterator<Item = (Point3, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The up to 6 neighbours of `p` sharing a face with it.
+    pub fn neighbours6(&self, p: Point3) -> impl Iterator<Item = (Point3, &T)> {
+        neighbours(p, &FACES, move |p| self.get(p))
+    }
+
+    /// The up to 26 neighbours of `p` sharing a face, edge or corner with it.
+    pub fn neighbours26(&self, p: Point3) -> impl Iterator<Item = (Point3, &T)> {
+        neighbours(p, &ADJACENT3, move |p| self.get(p))
+    }
+}
+
+/// A sparse grid of voxels, unbounded in every direction.
+pub struct InfiniteGrid3<T> {
+    inner: HashMap<Point3, T>,
+    /// The bounding box of all points in `inner`, `None` when empty. Too large while `stale`.
+    bounds: Cell<Option<Cuboid>>,
+    /// Whether a point on the edge of `bounds` was removed since they were computed.
+    stale: Cell<bool>,
+}
+
+impl<T> InfiniteGrid3<T> {
+    pub fn new() -> Self {
+        Self {
+            inner: HashMap::new(),
+            bounds: Cell::new(None),
+            stale: Cell::new(false),
+        }
+    }
+
+    /// The entry of `p`, for inserting or updating it.
+    pub fn entry(&mut self, p: Point3) -> Entry3<'_, T> {
+        Entry3 { grid: self, key: p }
+    }
+
+    pub fn insert(&mut self, p: Point3, v: T) -> Option<T> {
+        self.extend_bounds(p);
+        self.inner.insert(p, v)
+    }
+
+    /// Removes `p`. The bounds shrink the next time they are needed if it was on their edge.
+    pub fn remove(&mut self, p: Point3) -> Option<T> {
+        let removed = self.inner.remove(&p)?;
+
+        if let Some(Cuboid { min, max }) = self.bounds.get() {
+            if [p.x, p.y, p.z]
+                .into_iter()
+                .zip([(min.x, max.x), (min.y, max.y), (min.z, max.z)])
+                .any(|(c, (min, max))| c == min || c == max)
+            {
+                self.stale.set(true);
+            }
+        }
+
+        Some(removed)
+    }
+
+    fn extend_bounds(&mut self, p: Point3) {
+        self.bounds.set(Some(match self.bounds.get() {
+            Some(bounds) => bounds.extend(p),
+            None => Cuboid::point(p),
+        }));
+    }
+
+    pub fn get(&self, p: Point3) -> Option<&T> {
+        self.inner.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point3) -> Option<&mut T> {
+        self.inner.get_mut(&p)
+    }
+
+    /// The occupied points, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point3, &T)> {
+        self.inner.iter().map(|(&p, v)| (p, v))
+    }
+
+    /// The occupied neighbours of `p` sharing a face with it.
+    pub fn neighbours6(&self, p: Point3) -> impl Iterator<Item = (Point3, &T)> {
+        neighbours(p, &FACES, move |p| self.get(p))
+    }
+
+    /// The occupied neighbours of `p` sharing a face, edge or corner with it.
+    pub fn neighbours26(&self, p: Point3) -> impl Iterator<Item = (Point3, &T)> {
+        neighbours(p, &ADJACENT3, move |p| self.get(p))
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// The bounding box of all points, `None` when the grid is empty.
+    ///
+    /// Recomputed from all points after a point on the edge was removed.
+    pub fn dimensions(&self) -> Option<Cuboid> {
+        if self.stale.replace(false) {
+            self.bounds
+                .set(self.inner.keys().fold(None, |bounds: Option<Cuboid>, &p| {
+                    Some(bounds.map_or(Cuboid::point(p), |bounds| bounds.extend(p)))
+                }));
+        }
+
+        self.bounds.get()
+    }
+}
+
+impl<T> Default for InfiniteGrid3<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Point3, T)> for InfiniteGrid3<T> {
+    fn from_iter<I: IntoIterator<Item = (Point3, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+
+        for (p, v) in iter {
+            grid.insert(p, v);
+        }
+
+        grid
+    }
+}
+
+/// A point of an [`InfiniteGrid3`], for inserting or updating its value.
+pub struct Entry3<'a, T> {
+    grid: &'a mut InfiniteGrid3<T>,
+    key: Point3,
+}
+
+impl<'a, T> Entry3<'a, T> {
+    pub fn key(&self) -> Point3 {
+        self.key
+    }
+
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'a mut T {
+        self.grid.extend_bounds(self.key);
+        self.grid.inner.entry(self.key).or_insert_with(f)
+    }
+
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Updates the value with `f` if there is one.
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(v) = self.grid.inner.get_mut(&self.key) {
+            f(v);
+        }
+
+        self
+    }
+}
+
+impl<T> Cells<T, Point3> for Grid3<T> {
+    fn cell(&self, p: Point3) -> Option<&T> {
+        self.get(p)
+    }
+
+    fn bounds(&self) -> Option<Cuboid> {
+        let (width, height, depth) = self.size();
+
+        (width > 0 && height > 0 && depth > 0).then(|| {
+            Cuboid::new(
+                Point3::ZERO,
+                Point3::new(width as i32 - 1, height as i32 - 1, depth as i32 - 1),
+            )
+        })
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point3, &T)> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+impl<T> Cells<T, Point3> for InfiniteGrid3<T> {
+    fn cell(&self, p: Point3) -> Option<&T> {
+        self.get(p)
+    }
+
+    fn bounds(&self) -> Option<Cuboid> {
+        self.dimensions()
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point3, &T)> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+/// The number of faces between a `solid` voxel and one which is not, counting pockets of air
+/// trapped inside.
+pub fn surface_area<T>(grid: &impl Cells<T, Point3>, solid: impl Fn(&T) -> bool) -> usize {
+    grid.cells()
+        .filter(|(_, v)| solid(v))
+        .map(|(p, _)| {
+            FACES
+                .iter()
+                .filter(|&&d| !grid.cell(p + d).is_some_and(&solid))
+                .count()
+        })
+        .sum()
+}
+
+/// The points outside of the solid voxels of a grid, see [`exterior`].
+///
+/// Space is cut into slabs along every axis: one for every coordinate of a solid voxel, one for
+/// every gap between them and one on either side. A box made of slabs holds no solid voxel unless
+/// it is a single point, so empty space is filled a whole box at a time.
+#[derive(Debug, Clone)]
+pub struct Exterior {
+    /// The first coordinate of every slab along `x`, `y` and `z`. The last slabs are one wide.
+    slabs: [Vec<i32>; 3],
+    /// Whether every box is outside, `x` fastest.
+    outside: Vec<bool>,
+}
+
+impl Exterior {
+    /// The slabs of the box holding `p`, `None` beyond the outermost slabs.
+    fn find(&self, p: Point3) -> Option<[usize; 3]> {
+        let mut slab = [0; 3];
+
+        for (axis, c) in [p.x, p.y, p.z].into_iter().enumerate() {
+            let starts = &self.slabs[axis];
+
+            if c > *starts.last()? {
+                return None;
+            }
+            slab[axis] = starts.partition_point(|&s| s <= c).checked_sub(1)?;
+        }
+
+        Some(slab)
+    }
+
+    fn index(&self, [x, y, z]: [usize; 3]) -> usize {
+        (z * self.slabs[1].len() + y) * self.slabs[0].len() + x
+    }
+
+    /// The only point of a box, `None` if it holds more than one.
+    fn single(&self, slab: [usize; 3]) -> Option<Point3> {
+        let mut p = [0; 3];
+
+        for axis in 0..3 {
+            let starts = &self.slabs[axis];
+            let start = starts[slab[axis]];
+
+            if starts
+                .get(slab[axis] + 1)
+                .is_some_and(|&next| next > start + 1)
+            {
+                return None;
+            }
+            p[axis] = start;
+        }
+
+        Some(Point3::new(p[0], p[1], p[2]))
+    }
+
+    /// Whether `p` is not solid and can be reached through faces from beyond the solid voxels.
+    pub fn contains(&self, p: Point3) -> bool {
+        self.find(p)
+            .is_none_or(|slab| self.outside[self.index(slab)])
+    }
+}
+
+/// The first coordinates of the slabs along an axis, given the coordinates of the solid voxels on
+/// it.
+fn slabs(coords: impl Iterator<Item = i32>) -> Vec<i32> {
+    let mut coords = coords.collect::<Vec<_>>();
+    coords.sort_unstable();
+    coords.dedup();
+
+    let mut starts = vec![];
+    let mut prev = None;
+
+    for c in coords {
+        match prev {
+            None => starts.push(c - 1),
+            Some(prev) if c > prev + 1 => starts.push(prev + 1),
+            Some(_) => {}
+        }
+
+        starts.push(c);
+        prev = Some(c);
+    }
+
+    starts.extend(prev.map(|last| last + 1));
+    starts
+}
+
+/// The points which are not `solid` and can be reached through faces from beyond the solid voxels
+/// of `grid`.
+///
+/// Takes time in the number of solid voxels and the gaps between their coordinates rather than in
+/// the volume they span.
+pub fn exterior<T>(grid: &impl Cells<T, Point3>, solid: impl Fn(&T) -> bool) -> Exterior {
+    let voxels = grid
+        .cells()
+        .filter(|(_, v)| solid(v))
+        .map(|(p, _)| [p.x, p.y, p.z])
+        .collect::<Vec<_>>();
+
+    let slabs = [0, 1, 2].map(|axis| slabs(voxels.iter().map(|p| p[axis])));
+    let size = [0, 1, 2].map(|axis| slabs[axis].len());
+    let mut exterior = Exterior {
+        outside: vec![false; size.iter().product()],
+        slabs,
+    };
+
+    if voxels.is_empty() {
+        return exterior;
+    }
+
+    // The first box is the corner beyond the lowest voxel on every axis.
+    exterior.outside[0] = true;
+    let mut stack = vec![[0usize; 3]];
+
+    while let Some(slab) = stack.pop() {
+        for axis in 0..3 {
+            for next in [slab[axis].wrapping_sub(1), slab[axis] + 1] {
+                if next >= size[axis] {
+                    continue;
+                }
+
+                let mut n = slab;
+                n[axis] = next;
+                let i = exterior.index(n);
+
+                let solid = exterior
+                    .single(n)
+                    .is_some_and(|p| grid.cell(p).is_some_and(&solid));
+
+                if !exterior.outside[i] && !solid {
+                    exterior.outside[i] = true;
+                    stack.push(n);
+                }
+            }
+        }
+    }
+
+    exterior
+}
+
+/// The number of faces between a `solid` voxel and the outside, leaving out pockets of air
+/// trapped inside.
+pub fn exterior_surface_area<T>(grid: &impl Cells<T, Point3>, solid: impl Fn(&T) -> bool) -> usize {
+    let outside = exterior(grid, &solid);
+
+    grid.cells()
+        .filter(|(_, v)| solid(v))
+        .map(|(p, _)| FACES.iter().filter(|&&d| outside.contains(p + d)).count())
+        .sum()
+}
+
+#[test]
+fn test_point3() {
+    use std::collections::HashSet;
+
+    let a = Point3::new(1, -2, 3);
+    let b = Point3::new(-1, 2, 0);
+
+    assert_eq!(a + b, Point3::new(0, 0, 3));
+    assert_eq!(a - b, Point3::new(2, -4, 3));
+    assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+    assert_eq!(a.manhattan(b), 9);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!(a.step_toward(b), Point3::new(0, -1, 2));
+    assert_eq!(
+        a.clamp(Point3::ZERO, Point3::new(2, 2, 2)),
+        Point3::new(1, 0, 2)
+    );
+
+    assert_eq!(ADJACENT3.iter().collect::<HashSet<_>>().len(), 26);
+    assert!(ADJACENT3.iter().all(|&d| d.chebyshev(Point3::ZERO) == 1));
+    assert!(FACES.iter().all(|d| ADJACENT3.contains(d)));
+}
+
+#[test]
+fn test_voxels() {
+    let mut dense = Grid3::filled(3, 2, 2, 0);
+    *dense.get_mut(Point3::new(2, 1, 1)).unwrap() = 7;
+
+    assert_eq!(dense.iter().last(), Some((Point3::new(2, 1, 1), &7)));
+    assert_eq!(dense.get(Point3::new(3, 0, 0)), None);
+    assert_eq!(dense.neighbours6(Point3::ZERO).count(), 3);
+    assert_eq!(dense.neighbours26(Point3::new(1, 0, 0)).count(), 11);
+
+    let sparse = [(1, 1, 1), (2, 1, 1)]
+        .into_iter()
+        .map(|p| (Point3::from(p), ()))
+        .collect::<InfiniteGrid3<_>>();
+
+    assert_eq!(
+        sparse.dimensions(),
+        Some(Cuboid::new(Point3::new(1, 1, 1), Point3::new(2, 1, 1)))
+    );
+    assert_eq!(sparse.neighbours6(Point3::new(1, 1, 1)).count(), 1);
+    assert_eq!(surface_area(&sparse, |_| true), 10);
+}
+
+#[test]
+fn test_surface_area() {
+    let droplet = "2,2,2 1,2,2 3,2,2 2,1,2 2,3,2 2,2,1 2,2,3 2,2,4 2,2,6 1,2,5 3,2,5 2,1,5 2,3,5";
+
+    let lava = droplet
+        .split(' ')
+        .map(|p| {
+            let p = p.split(',').map(|c| c.parse().unwrap()).collect::<Vec<_>>();
+            (Point3::new(p[0], p[1], p[2]), ())
+        })
+        .collect::<InfiniteGrid3<_>>();
+
+    let solid = |_: &()| true;
+
+    assert_eq!(surface_area(&lava, solid), 64);
+    assert_eq!(exterior_surface_area(&lava, solid), 58);
+
+    // The same droplet as a dense grid.
+    let max = lava.dimensions().unwrap().max;
+    let mut dense = Grid3::filled(
+        max.x as usize + 1,
+        max.y as usize + 1,
+        max.z as usize + 1,
+        false,
+    );
+    for (p, _) in lava.iter() {
+        *dense.get_mut(p).unwrap() = true;
+    }
+
+    let solid = |&v: &bool| v;
+    assert_eq!(surface_area(&dense, solid), 64);
+    assert_eq!(exterior_surface_area(&dense, solid), 58);
+    let outside = exterior(&dense, solid);
+    assert!(!outside.contains(Point3::new(2, 2, 5)));
+    assert!(!outside.contains(Point3::new(2, 2, 2)));
+    assert!(outside.contains(Point3::new(0, 0, 0)));
+    assert!(outside.contains(Point3::new(-100, 2, 2)));
+}
+
+#[test]
+fn test_far_apart_voxels() {
+    // Only the voxels are visited, not the space between them.
+    let far = 1_000_000;
+    let mut grid = [(0, 0, 0), (far, far, far), (far, far, far + 1)]
+        .into_iter()
+        .map(|p| (Point3::from(p), ()))
+        .collect::<InfiniteGrid3<_>>();
+
+    assert_eq!(surface_area(&grid, |_| true), 16);
+    assert_eq!(exterior_surface_area(&grid, |_| true), 16);
+    assert!(exterior(&grid, |_| true).contains(Point3::new(far / 2, 0, far)));
+
+    grid.remove(Point3::new(far, far, far + 1));
+    *grid.entry(Point3::new(0, 0, 1)).or_default() = ();
+    assert_eq!(grid.num_points(), 3);
+    assert_eq!(
+        grid.dimensions(),
+        Some(Cuboid::new(Point3::ZERO, Point3::new(far, far, far)))
+    );
+    assert_eq!(surface_area(&grid, |_| true), 16);
+
+    grid.remove(Point3::new(far, far, far));
+    assert_eq!(
+        grid.dimensions(),
+        Some(Cuboid::new(Point3::ZERO, Point3::new(0, 0, 1)))
+    );
+}
@@ -0,0 +1,272 @@
+use std::{cell::Cell, collections::HashMap};
+
+use super::Point;
+
+/// Where an [`InfiniteGrid`](super::InfiniteGrid) keeps its cells.
+pub trait Storage<T>: Default {
+    fn get(&self, p: Point) -> Option<&T>;
+
+    fn get_mut(&mut self, p: Point) -> Option<&mut T>;
+
+    /// The value at `p`, inserting the one made by `f` if there is none.
+    fn get_or_insert_with(&mut self, p: Point, f: impl FnOnce() -> T) -> &mut T;
+
+    fn insert(&mut self, p: Point, v: T) -> Option<T>;
+
+    fn remove(&mut self, p: Point) -> Option<T>;
+
+    /// Removes every point, keeping allocations around for reuse where possible.
+    fn clear(&mut self);
+
+    /// The number of occupied points.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The occupied points, in no particular order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_>;
+}
+
+impl<T> Storage<T> for HashMap<Point, T> {
+    fn get(&self, p: Point) -> Option<&T> {
+        HashMap::get(self, &p)
+    }
+
+    fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        HashMap::get_mut(self, &p)
+    }
+
+    fn get_or_insert_with(&mut self, p: Point, f: impl FnOnce() -> T) -> &mut T {
+        self.entry(p).or_insert_with(f)
+    }
+
+    fn insert(&mut self, p: Point, v: T) -> Option<T> {
+        HashMap::insert(self, p, v)
+    }
+
+    fn remove(&mut self, p: Point) -> Option<T> {
+        HashMap::remove(self, &p)
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        Box::new(HashMap::iter(self).map(|(&p, v)| (p, v)))
+    }
+}
+
+/// Width and height of a [`Chunked`] tile.
+const TILE: i32 = 16;
+
+struct Tile<T> {
+    /// The position of the tile, in tiles.
+    at: Point,
+    /// The cells of the tile, row by row.
+    cells: Box<[Option<T>]>,
+}
+
+/// Sparse storage in dense square tiles, allocated when a point in them is first inserted.
+///
+/// Neighbouring points share a tile, so walking around only looks up a tile when crossing into
+/// another one.
+pub struct Chunked<T> {
+    tiles: Vec<Tile<T>>,
+    /// The index in `tiles` of every tile position.
+    index: HashMap<Point, usize>,
+    /// The last tile looked up, with its index in `tiles`.
+    last: Cell<Option<(Point, usize)>>,
+    len: usize,
+    /// The cells of cleared tiles, emptied and ready to be reused.
+    spare: Vec<Box<[Option<T>]>>,
+}
+
+impl<T> Default for Chunked<T> {
+    fn default() -> Self {
+        Self {
+            tiles: vec![],
+            index: HashMap::new(),
+            last: Cell::new(None),
+            len: 0,
+            spare: vec![],
+        }
+    }
+}
+
+impl<T> Chunked<T> {
+    /// The position of the tile holding `p`, and the offset of `p` in it.
+    fn locate(p: Point) -> (Point, usize) {
+        let at = Point::new(p.x.div_euclid(TILE), p.y.div_euclid(TILE));
+        let offset = p.y.rem_euclid(TILE) * TILE + p.x.rem_euclid(TILE);
+
+        (at, offset as usize)
+    }
+
+    /// The index of the tile at `at`, if it was allocated.
+    fn tile(&self, at: Point) -> Option<usize> {
+        if let Some((last, i)) = self.last.get() {
+            if last == at {
+                return Some(i);
+            }
+        }
+
+        let i = *self.index.get(&at)?;
+        self.last.set(Some((at, i)));
+        Some(i)
+    }
+
+    /// The index of the tile at `at`, allocating it if `create` is set.
+    fn tile_mut(&mut self, at: Point, create: bool) -> Option<usize> {
+        match self.tile(at) {
+            Some(i) => Some(i),
+            None if create => {
+                let cells = self
+                    .spare
+                    .pop()
+                    .unwrap_or_else(|| (0..TILE * TILE).map(|_| None).collect());
+                let i = self.tiles.len();
+                self.tiles.push(Tile { at, cells });
+                self.index.insert(at, i);
+                self.last.set(Some((at, i)));
+                Some(i)
+            }
+            None => None,
+        }
+    }
+}
+
+impl<T> Storage<T> for Chunked<T> {
+    fn get(&self, p: Point) -> Option<&T> {
+        let (at, offset) = Self::locate(p);
+        let i = self.tile(at)?;
+
+        self.tiles[i].cells[offset].as_ref()
+    }
+
+    fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        let (at, offset) = Self::locate(p);
+        let i = self.tile_mut(at, false)?;
+
+        self.tiles[i].cells[offset].as_mut()
+    }
+
+    fn get_or_insert_with(&mut self, p: Point, f: impl FnOnce() -> T) -> &mut T {
+        let (at, offset) = Self::locate(p);
+        let i = self.tile_mut(at, true).expect("created");
+        let cell = &mut self.tiles[i].cells[offset];
+
+        if cell.is_none() {
+            self.len += 1;
+        }
+
+        cell.get_or_insert_with(f)
+    }
+
+    fn insert(&mut self, p: Point, v: T) -> Option<T> {
+        let (at, offset) = Self::locate(p);
+        let i = self.tile_mut(at, true).expect("created");
+        let old = self.tiles[i].cells[offset].replace(v);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    fn remove(&mut self, p: Point) -> Option<T> {
+        let (at, offset) = Self::locate(p);
+        let i = self.tile_mut(at, false)?;
+        let old = self.tiles[i].cells[offset].take();
+
+        if old.is_some() {
+            self.len -= 1;
+        }
+
+        old
+    }
+
+    fn clear(&mut self) {
+        for mut tile in self.tiles.drain(..) {
+            tile.cells.iter_mut().for_each(|cell| *cell = None);
+            self.spare.push(tile.cells);
+        }
+
+        self.index.clear();
+        self.last.set(None);
+        self.len = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        Box::new(self.tiles.iter().flat_map(|tile| {
+            tile.cells.iter().enumerate().filter_map(move |(i, cell)| {
+                let p = Point::new(
+                    tile.at.x * TILE + i as i32 % TILE,
+                    tile.at.y * TILE + i as i32 / TILE,
+                );
+
+                cell.as_ref().map(|v| (p, v))
+            })
+        }))
+    }
+}
+
+#[test]
+fn test_chunked() {
+    let mut chunked = Chunked::default();
+    let mut map = HashMap::new();
+
+    // Cross tile borders in every direction, including negative coordinates.
+    let points = (-40..40)
+        .step_by(3)
+        .flat_map(|x| [Point::new(x, x / 2), Point::new(-x, 7)]);
+
+    for (i, p) in points.enumerate() {
+        assert_eq!(chunked.insert(p, i), Storage::insert(&mut map, p, i));
+        *chunked.get_or_insert_with(p + Point::new(1, 0), || 0) += i;
+        *map.get_or_insert_with(p + Point::new(1, 0), || 0) += i;
+    }
+
+    for p in [
+        Point::new(-40, -20),
+        Point::new(-41, -20),
+        Point::new(1000, 0),
+    ] {
+        assert_eq!(chunked.remove(p), Storage::remove(&mut map, p));
+    }
+
+    assert_eq!(chunked.len(), map.len());
+    assert_eq!(chunked.get(Point::new(-40, -20)), None);
+    assert_eq!(chunked.get(Point::new(-39, -20)), Some(&0));
+    assert_eq!(chunked.get(Point::new(-41, -20)), None);
+    assert_eq!(chunked.get(Point::new(1000, 1000)), None);
+
+    let mut cells = chunked.iter().map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    let mut expected = Storage::iter(&map)
+        .map(|(p, &v)| (p, v))
+        .collect::<Vec<_>>();
+    cells.sort_by_key(|&(p, _)| (p.x, p.y));
+    expected.sort_by_key(|&(p, _)| (p.x, p.y));
+
+    assert_eq!(cells, expected);
+
+    chunked.clear();
+    assert_eq!((chunked.len(), chunked.iter().count()), (0, 0));
+    assert_eq!(chunked.get(Point::new(-39, -20)), None);
+    chunked.insert(Point::new(100, 100), 1);
+    assert_eq!(
+        chunked.iter().collect::<Vec<_>>(),
+        [(Point::new(100, 100), &1)]
+    );
+}
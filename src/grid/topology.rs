@@ -0,0 +1,538 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::{bail, Result};
+
+use super::{Direction, Grid, Point, Point3};
+
+/// How moving from cell to cell works, including off the edges of a grid.
+pub trait Topology {
+    /// The cell reached by one step from `from` facing `dir`, and the direction faced after the
+    /// step. `None` when the step leaves the grid, or cannot be taken on it.
+    fn step(&self, from: Point, dir: Direction) -> Option<(Point, Direction)>;
+}
+
+impl<T> Grid<T> {
+    /// One step from `from` facing `dir` through `topology`, with the cell reached and the
+    /// direction faced after the step.
+    pub fn step(
+        &self,
+        topology: &impl Topology,
+        from: Point,
+        dir: Direction,
+    ) -> Option<(Point, Direction, &T)> {
+        let (to, dir) = topology.step(from, dir)?;
+
+        self.get_point(to).map(|cell| (to, dir, cell))
+    }
+}
+
+/// Steps stop at the edges of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounded {
+    width: i32,
+    height: i32,
+}
+
+impl Bounded {
+    pub fn of<T>(grid: &Grid<T>) -> Self {
+        Self {
+            width: grid.width() as i32,
+            height: grid.height() as i32,
+        }
+    }
+}
+
+impl Topology for Bounded {
+    fn step(&self, from: Point, dir: Direction) -> Option<(Point, Direction)> {
+        let to = from + dir.offset();
+        let inside = (0..self.width).contains(&to.x) && (0..self.height).contains(&to.y);
+
+        inside.then_some((to, dir))
+    }
+}
+
+/// Stepping off an edge of the grid comes back in on the opposite edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Torus {
+    width: i32,
+    height: i32,
+}
+
+impl Torus {
+    pub fn of<T>(grid: &Grid<T>) -> Self {
+        assert!(
+            grid.width() > 0 && grid.height() > 0,
+            "a torus needs a non-empty grid"
+        );
+
+        Self {
+            width: grid.width() as i32,
+            height: grid.height() as i32,
+        }
+    }
+}
+
+impl Topology for Torus {
+    fn step(&self, from: Point, dir: Direction) -> Option<(Point, Direction)> {
+        let to = from + dir.offset();
+
+        Some((
+            Point::new(to.x.rem_euclid(self.width), to.y.rem_euclid(self.height)),
+            dir,
+        ))
+    }
+}
+
+/// A face of a [`CubeNet`], placed on the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    /// The position of the face in the net, in faces.
+    block: Point,
+    /// Pointing out of the cube.
+    normal: Point3,
+    /// Pointing along the rows of the face in the net.
+    right: Point3,
+    /// Pointing along the columns of the face in the net.
+    down: Point3,
+}
+
+impl Face {
+    /// The direction on the cube of moving `dir` on this face, `None` for diagonals.
+    fn towards(&self, dir: Direction) -> Option<Point3> {
+        match dir {
+            Direction::N => Some(-self.down),
+            Direction::E => Some(self.right),
+            Direction::S => Some(self.down),
+            Direction::W => Some(-self.right),
+            _ => None,
+        }
+    }
+
+    /// The neighbour of this face in direction `dir` of the net, once folded.
+    fn fold(&self, dir: Direction) -> Face {
+        let block = self.block + dir.offset();
+        let normal = self
+            .towards(dir)
+            .expect("faces are folded along orthogonal edges");
+
+        match dir {
+            Direction::N | Direction::S => Face {
+                block,
+                normal,
+                right: self.right,
+                down: if dir == Direction::S {
+                    -self.normal
+                } else {
+                    self.normal
+                },
+            },
+            _ => Face {
+                block,
+                normal,
+                right: if dir == Direction::E {
+                    -self.normal
+                } else {
+                    self.normal
+                },
+                down: self.down,
+            },
+        }
+    }
+}
+
+fn dot(a: Point3, b: Point3) -> i32 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+/// A map made of the 6 square faces of a cube, laid out flat. Stepping off the edge of a face
+/// continues on the face it is folded against, e.g.
+///
+/// ```text
+///   #
+/// ####
+///   #
+///   #
+/// ```
+///
+/// Diagonal steps can only be taken within a face.
+#[derive(Debug, Clone)]
+pub struct CubeNet {
+    /// Width and height of a face, in cells.
+    size: i32,
+    faces: Vec<Face>,
+    /// The index in `faces` of the face at every position in the net.
+    blocks: HashMap<Point, usize>,
+}
+
+impl CubeNet {
+    /// Infers the net from the cells of `grid` which are part of a face, according to `on_face`.
+    pub fn infer<T>(grid: &Grid<T>, on_face: impl Fn(&T) -> bool) -> Result<Self> {
+        let cells = grid.iter().filter(|(_, v)| on_face(v)).count();
+        let size = (1..=cells)
+            .find(|s| 6 * s * s >= cells)
+            .filter(|s| 6 * s * s == cells);
+
+        let Some(size) = size else {
+            bail!("Expected the cells of 6 square faces, got {} cells", cells);
+        };
+
+        let mut blocks = vec![];
+
+        for by in 0..grid.height() / size {
+            for bx in 0..grid.width() / size {
+                let block = (0..size)
+                    .flat_map(|y| (0..size).map(move |x| (bx * size + x, by * size + y)))
+                    .map(|idx| on_face(&grid[idx]))
+                    .collect::<Vec<_>>();
+
+                if block.iter().all(|&v| v) {
+                    blocks.push(Point::new(bx as i32, by as i32));
+                } else if block.iter().any(|&v| v) {
+                    bail!(
+                        "Expected face ({}, {}) of size {} to be either full or empty",
+                        bx,
+                        by,
+                        size
+                    );
+                }
+            }
+        }
+
+        if blocks.len() != 6 {
+            bail!(
+                "Expected the cells to be laid out as 6 faces of size {}, got {}",
+                size,
+                blocks.len()
+            );
+        }
+
+        // Fold the net around the first face.
+        let mut faces = HashMap::from([(
+            blocks[0],
+            Face {
+                block: blocks[0],
+                normal: Point3::new(0, 0, 1),
+                right: Point3::new(1, 0, 0),
+                down: Point3::new(0, 1, 0),
+            },
+        )]);
+        let mut queue = VecDeque::from([blocks[0]]);
+
+        while let Some(block) = queue.pop_front() {
+            let face = faces[&block];
+
+            for dir in Direction::ORTHOGONAL {
+                let next = block + dir.offset();
+
+                if blocks.contains(&next) && !faces.contains_key(&next) {
+                    faces.insert(next, face.fold(dir));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if faces.len() != 6 {
+            bail!("Expected the faces of the net to be connected");
+        }
+
+        let faces = blocks.iter().map(|b| faces[b]).collect::<Vec<_>>();
+
+        for (i, face) in faces.iter().enumerate() {
+            if faces[..i].iter().any(|f| f.normal == face.normal) {
+                bail!(
+                    "Face ({}, {}) overlaps another face when folded, not a cube net",
+                    face.block.x,
+                    face.block.y
+                );
+            }
+        }
+
+        Ok(Self {
+            size: size as i32,
+            blocks: blocks.iter().enumerate().map(|(i, &b)| (b, i)).collect(),
+            faces,
+        })
+    }
+
+    /// The face holding `p`, if any.
+    fn face(&self, p: Point) -> Option<&Face> {
+        let block = Point::new(p.x.div_euclid(self.size), p.y.div_euclid(self.size));
+
+        self.blocks.get(&block).map(|&i| &self.faces[i])
+    }
+}
+
+impl Topology for CubeNet {
+    fn step(&self, from: Point, dir: Direction) -> Option<(Point, Direction)> {
+        let face = self.face(from)?;
+        let to = from + dir.offset();
+
+        if self
+            .face(to)
+            .is_some_and(|f| f == face || !dir.is_diagonal())
+        {
+            return Some((to, dir));
+        }
+
+        // The centres of cells on the cube, with a cell 2 units wide and the cube centred on
+        // the origin.
+        let s = self.size;
+        let local = from - face.block * s;
+        let centre = face.normal * s
+            + face.right * (2 * local.x + 1 - s)
+            + face.down * (2 * local.y + 1 - s);
+
+        // Half a cell to the edge, and half a cell down the other side.
+        let out = face.towards(dir)?;
+        let centre = centre + out - face.normal;
+
+        let next = self
+            .faces
+            .iter()
+            .find(|f| f.normal == out)
+            .expect("every side of a cube has a face");
+        let dir = Direction::ORTHOGONAL
+            .into_iter()
+            .find(|&d| next.towards(d) == Some(-face.normal))
+            .expect("faces meet at an edge");
+
+        let local = Point::new(
+            (dot(centre, next.right) + s - 1) / 2,
+            (dot(centre, next.down) + s - 1) / 2,
+        );
+
+        Some((next.block * s + local, dir))
+    }
+}
+
+#[test]
+fn test_bounded_and_torus() {
+    let grid = Grid::filled(3, 2, ());
+
+    let bounded = Bounded::of(&grid);
+    assert_eq!(
+        bounded.step(Point::new(1, 1), Direction::N),
+        Some((Point::new(1, 0), Direction::N))
+    );
+    assert_eq!(bounded.step(Point::new(2, 1), Direction::E), None);
+    assert_eq!(bounded.step(Point::new(0, 0), Direction::NW), None);
+
+    let torus = Torus::of(&grid);
+    assert_eq!(
+        torus.step(Point::new(2, 1), Direction::E),
+        Some((Point::new(0, 1), Direction::E))
+    );
+    assert_eq!(
+        torus.step(Point::new(0, 0), Direction::NW),
+        Some((Point::new(2, 1), Direction::NW))
+    );
+    assert_eq!(
+        grid.step(&torus, Point::new(1, 0), Direction::N),
+        Some((Point::new(1, 1), Direction::N, &()))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    type Shape = BTreeSet<(i32, i32)>;
+
+    fn normalize(shape: impl IntoIterator<Item = (i32, i32)>) -> Shape {
+        let shape = shape.into_iter().collect::<Vec<_>>();
+        let min_x = shape.iter().map(|p| p.0).min().unwrap();
+        let min_y = shape.iter().map(|p| p.1).min().unwrap();
+
+        shape.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect()
+    }
+
+    /// Every placement of polyominoes of `n` squares, up to translation.
+    fn polyominoes(n: usize) -> BTreeSet<Shape> {
+        let mut shapes = BTreeSet::from([Shape::from([(0, 0)])]);
+
+        for _ in 1..n {
+            shapes = shapes
+                .iter()
+                .flat_map(|shape| {
+                    shape.iter().flat_map(move |&(x, y)| {
+                        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                            .into_iter()
+                            .map(move |(dx, dy)| (x + dx, y + dy))
+                            .filter(|p| !shape.contains(p))
+                            .map(move |p| normalize(shape.iter().copied().chain([p])))
+                    })
+                })
+                .collect();
+        }
+
+        shapes
+    }
+
+    /// The same shape up to rotation and reflection.
+    fn canonical(shape: &Shape) -> Shape {
+        let mut shape = shape.clone();
+        let mut smallest = shape.clone();
+
+        for _ in 0..2 {
+            for _ in 0..4 {
+                shape = normalize(shape.iter().map(|&(x, y)| (-y, x)));
+                smallest = smallest.min(shape.clone());
+            }
+
+            shape = normalize(shape.iter().map(|&(x, y)| (-x, y)));
+        }
+
+        smallest
+    }
+
+    /// A map of `shape` with faces of `size` cells.
+    fn net(shape: &Shape, size: usize) -> Grid<bool> {
+        let width = shape.iter().map(|p| p.0).max().unwrap() as usize + 1;
+        let height = shape.iter().map(|p| p.1).max().unwrap() as usize + 1;
+
+        let mut grid = Grid::filled(width * size, height * size, false);
+        for ((x, y), cell) in grid.iter_mut() {
+            *cell = shape.contains(&((x / size) as i32, (y / size) as i32));
+        }
+
+        grid
+    }
+
+    #[test]
+    fn test_cube_nets() {
+        const SIZE: usize = 3;
+
+        let hexominoes = polyominoes(6);
+        assert_eq!(hexominoes.len(), 216);
+
+        let mut nets = BTreeSet::new();
+
+        for shape in &hexominoes {
+            let grid = net(shape, SIZE);
+            let Ok(cube) = CubeNet::infer(&grid, |&v| v) else {
+                continue;
+            };
+
+            nets.insert(canonical(shape));
+
+            for p in grid.points().filter(|&p| grid[p]) {
+                for dir in Direction::ORTHOGONAL {
+                    let (to, facing) = cube.step(p, dir).unwrap();
+                    assert!(grid[to], "{:?} stepped off the net to {:?}", shape, to);
+
+                    // Steps can be retraced.
+                    assert_eq!(cube.step(to, facing.reverse()), Some((p, dir.reverse())));
+
+                    // Going straight on goes once around the cube.
+                    let around = (0..4 * SIZE).fold((p, dir), |(p, d), _| cube.step(p, d).unwrap());
+                    assert_eq!(around, (p, dir), "{:?} from {:?}", shape, p);
+                }
+            }
+        }
+
+        // There are 11 nets of a cube, up to rotation and reflection.
+        assert_eq!(nets.len(), 11);
+    }
+
+    #[test]
+    fn test_cube_net_diagonals() {
+        let shape = Shape::from([(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)]);
+        let grid = net(&shape, 2);
+        let cube = CubeNet::infer(&grid, |&v| v).unwrap();
+
+        // Within a face.
+        assert_eq!(
+            cube.step(Point::new(2, 0), Direction::SE),
+            Some((Point::new(3, 1), Direction::SE))
+        );
+
+        // Across the edge of a face, whether or not the net continues there.
+        for dir in [Direction::NE, Direction::SE, Direction::SW, Direction::NW] {
+            for p in grid.points().filter(|&p| grid[p]) {
+                let crosses = cube.face(p) != cube.face(p + dir.offset());
+                assert_eq!(cube.step(p, dir).is_none(), crosses, "{:?} {:?}", p, dir);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_net_errors() {
+        let grid = Grid::filled(6, 1, true);
+        let e = CubeNet::infer(&grid, |&v| v).unwrap_err();
+        assert!(e.to_string().contains("not a cube net"), "{}", e);
+
+        let grid = Grid::filled(5, 1, true);
+        assert!(CubeNet::infer(&grid, |&v| v).is_err());
+
+        let mut grid = net(
+            &Shape::from([(0, 0), (1, 0), (2, 0), (3, 0), (1, 1), (2, 2)]),
+            2,
+        );
+        assert!(CubeNet::infer(&grid, |&v| v).is_err());
+
+        grid[(1, 0)] = false;
+        assert!(CubeNet::infer(&grid, |&v| v).is_err());
+    }
+
+    #[test]
+    fn test_monkey_map() {
+        let map = "
+        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.";
+
+        let width = map.lines().map(str::len).max().unwrap();
+        let map = map
+            .lines()
+            .map(|l| format!("{:width$}", l))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let grid = Grid::parse(&map, Ok::<char, &str>).unwrap();
+        let cube = CubeNet::infer(&grid, |&c| c != ' ').unwrap();
+
+        let mut p = grid.points().find(|&p| grid[p] == '.').unwrap();
+        let mut dir = Direction::E;
+
+        for (steps, turn) in [
+            (10, 'R'),
+            (5, 'L'),
+            (5, 'R'),
+            (10, 'L'),
+            (4, 'R'),
+            (5, 'L'),
+            (5, ' '),
+        ] {
+            for _ in 0..steps {
+                match grid.step(&cube, p, dir).unwrap() {
+                    (_, _, '#') => break,
+                    (to, facing, _) => (p, dir) = (to, facing),
+                }
+            }
+
+            dir = match turn {
+                'R' => dir.turn_right(),
+                'L' => dir.turn_left(),
+                _ => dir,
+            };
+        }
+
+        let facing = [Direction::E, Direction::S, Direction::W, Direction::N]
+            .iter()
+            .position(|&d| d == dir)
+            .unwrap();
+
+        assert_eq!(1000 * (p.y + 1) + 4 * (p.x + 1) + facing as i32, 5031);
+    }
+}
@@ -0,0 +1,341 @@
+use std::ops::Index;
+
+use super::{neighbours, ray, Cells, Grid, Point, Rect, ADJACENT, ORTHOGONAL};
+
+/// A borrowed, possibly transformed, look at a [`Grid`], with its own `(x, y)` coordinates.
+///
+/// Transforms only change how the coordinates of the view map to those of the grid, so they can
+/// be chained freely without copying any cells.
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    /// The position in the grid of `(0, 0)`.
+    origin: Point,
+    /// The step in the grid when moving one column to the right in the view.
+    x_step: Point,
+    /// The step in the grid when moving one row down in the view.
+    y_step: Point,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<T> Grid<T> {
+    /// The whole grid as a [`View`].
+    pub fn view(&self) -> View<'_, T> {
+        View {
+            grid: self,
+            origin: Point::ZERO,
+            x_step: Point::new(1, 0),
+            y_step: Point::new(0, 1),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Swaps rows and columns, mirroring along the diagonal from the top left.
+    pub fn transpose(self) -> Self {
+        View {
+            x_step: self.y_step,
+            y_step: self.x_step,
+            width: self.height,
+            height: self.width,
+            ..self
+        }
+    }
+
+    /// Rotates by a quarter turn clockwise `quarter_turns` times, counter-clockwise when negative.
+    pub fn rotate(self, quarter_turns: i32) -> Self {
+        (0..quarter_turns.rem_euclid(4)).fold(self, |view, _| view.rotate_right())
+    }
+
+    /// A quarter turn clockwise: the left column becomes the top row.
+    fn rotate_right(self) -> Self {
+        View {
+            origin: self.origin + self.y_step * (self.height as i32 - 1),
+            x_step: -self.y_step,
+            y_step: self.x_step,
+            width: self.height,
+            height: self.width,
+            ..self
+        }
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(self) -> Self {
+        View {
+            origin: self.origin + self.x_step * (self.width as i32 - 1),
+            x_step: -self.x_step,
+            ..self
+        }
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(self) -> Self {
+        View {
+            origin: self.origin + self.y_step * (self.height as i32 - 1),
+            y_step: -self.y_step,
+            ..self
+        }
+    }
+
+    /// The part of the view within `area`, `None` unless it lies fully inside the view.
+    pub fn region(self, area: Rect) -> Option<Self> {
+        let inside = area.min.x >= 0
+            && area.min.y >= 0
+            && (area.max.x as usize) < self.width
+            && (area.max.y as usize) < self.height;
+
+        inside.then(|| View {
+            origin: self.locate(area.min),
+            width: area.width(),
+            height: area.height(),
+            ..self
+        })
+    }
+
+    /// The position in the grid of `p`, which must be in the view.
+    fn locate(&self, p: Point) -> Point {
+        self.origin + self.x_step * p.x + self.y_step * p.y
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.width as i32).contains(&p.x) && (0..self.height as i32).contains(&p.y)
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&'a T> {
+        self.get_point(Point::new(x.try_into().ok()?, y.try_into().ok()?))
+    }
+
+    pub fn get_point(&self, p: Point) -> Option<&'a T> {
+        if !self.contains(p) {
+            return None;
+        }
+
+        self.grid.get_point(self.locate(p))
+    }
+
+    /// The cell at `p`, panicking if it is outside of the view.
+    fn at(&self, p: Point) -> &'a T {
+        self.get_point(p).unwrap_or_else(|| {
+            panic!(
+                "{:?} out of bounds of a {}x{} view",
+                p, self.width, self.height
+            )
+        })
+    }
+
+    /// Every position as a [`Point`], row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i32;
+
+        (0..self.height as i32).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        let view = *self;
+
+        self.points()
+            .map(move |p| ((p.x as usize, p.y as usize), view.at(p)))
+    }
+
+    pub fn iter_row(&self, y: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator {
+        assert!(
+            y < self.height,
+            "row {} out of bounds of height {}",
+            y,
+            self.height
+        );
+
+        let view = *self;
+        (0..self.width as i32).map(move |x| view.at(Point::new(x, y as i32)))
+    }
+
+    pub fn iter_col(&self, x: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator {
+        assert!(
+            x < self.width,
+            "column {} out of bounds of width {}",
+            x,
+            self.width
+        );
+
+        let view = *self;
+        (0..self.height as i32).map(move |y| view.at(Point::new(x as i32, y)))
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator>
+           + ExactSizeIterator {
+        let view = *self;
+
+        (0..self.height).map(move |y| view.iter_row(y))
+    }
+
+    /// The up to 4 orthogonal neighbours of `p` within the view.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &'a T)> {
+        let view = *self;
+        neighbours(p, &ORTHOGONAL, move |p| view.get_point(p))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbours of `p` within the view.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &'a T)> {
+        let view = *self;
+        neighbours(p, &ADJACENT, move |p| view.get_point(p))
+    }
+
+    /// The cells from `from` (exclusive) in steps of `step` until the edge of the view.
+    pub fn ray(&self, from: Point, step: Point) -> impl Iterator<Item = (Point, &'a T)> {
+        let view = *self;
+        ray(from, step, move |p| view.get_point(p).map(Some))
+    }
+
+    /// Renders the view as text, one line per row.
+    pub fn render(&self, glyph: impl Fn(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            s.extend(row.map(&glyph));
+            s.push('\n');
+        }
+
+        s
+    }
+
+    /// Copies the cells of the view into a new grid.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.iter().map(|(_, v)| v.clone()).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.at(Point::new(x as i32, y as i32))
+    }
+}
+
+impl<T> Index<Point> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.at(p)
+    }
+}
+
+impl<T> Cells<T> for View<'_, T> {
+    fn cell(&self, p: Point) -> Option<&T> {
+        self.get_point(p)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        (self.width > 0 && self.height > 0).then(|| {
+            Rect::new(
+                Point::ZERO,
+                Point::new(self.width as i32 - 1, self.height as i32 - 1),
+            )
+        })
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        Box::new(self.points().map(|p| (p, self.at(p))))
+    }
+}
+
+#[test]
+fn test_views() {
+    let grid = Grid::parse("abc\ndef\n", Ok::<char, &str>).unwrap();
+    let render = |view: View<'_, char>| view.render(|&c| c);
+
+    assert_eq!(render(grid.view()), "abc\ndef\n");
+    assert_eq!(render(grid.view().transpose()), "ad\nbe\ncf\n");
+    assert_eq!(render(grid.view().rotate(1)), "da\neb\nfc\n");
+    assert_eq!(render(grid.view().rotate(2)), "fed\ncba\n");
+    assert_eq!(render(grid.view().rotate(-1)), "cf\nbe\nad\n");
+    assert_eq!(render(grid.view().rotate(4)), "abc\ndef\n");
+    assert_eq!(render(grid.view().flip_horizontal()), "cba\nfed\n");
+    assert_eq!(render(grid.view().flip_vertical()), "def\nabc\n");
+
+    // Transforms compose, e.g. a transpose is a flip of a quarter turn.
+    assert_eq!(
+        render(grid.view().rotate(1).flip_horizontal()),
+        render(grid.view().transpose())
+    );
+
+    let region = grid
+        .view()
+        .rotate(1)
+        .region(Rect::new(Point::new(0, 1), Point::new(1, 2)))
+        .unwrap();
+    assert_eq!(render(region), "eb\nfc\n");
+    assert_eq!(region[(1, 0)], 'b');
+    assert_eq!(region.get((2, 0)), None);
+    assert_eq!(region.iter_col(0).rev().collect::<String>(), "fe");
+    assert_eq!(
+        region
+            .neighbours4(Point::ZERO)
+            .map(|(_, &c)| c)
+            .collect::<String>(),
+        "bf"
+    );
+    assert_eq!(region.ray(Point::new(0, 0), Point::new(1, 1)).count(), 1);
+    assert!(grid
+        .view()
+        .region(Rect::new(Point::ZERO, Point::new(3, 0)))
+        .is_none());
+
+    assert_eq!(
+        region.iter().map(|(p, &c)| (p, c)).collect::<Vec<_>>(),
+        [((0, 0), 'e'), ((1, 0), 'b'), ((0, 1), 'f'), ((1, 1), 'c')]
+    );
+
+    // Views are searched, labelled and drawn like grids.
+    let components = crate::regions::components(
+        &region,
+        Cells::bounds(&region).unwrap(),
+        crate::regions::Connectivity::Four,
+        |_, c| c.is_some_and(|&c| c != 'b'),
+    );
+    assert_eq!(components.components.len(), 1);
+    assert_eq!(components.label(Point::new(1, 0)), None);
+    let mut drawn = String::new();
+    crate::render::render(
+        &mut drawn,
+        &region,
+        &Default::default(),
+        |_, c| *c.unwrap(),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(drawn, "eb\nfc\n");
+
+    assert_eq!(
+        region.to_grid(),
+        Grid::new(vec![vec!['e', 'b'], vec!['f', 'c']]).unwrap()
+    );
+}
@@ -0,0 +1,209 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::grid::{Cells, Point, Rect};
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone)]
+pub struct ImageOptions {
+    /// The area to draw. Defaults to the bounds of the grid.
+    pub viewport: Option<Rect>,
+    /// Width and height of the square drawn for each cell, in pixels.
+    pub scale: usize,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            viewport: None,
+            scale: 1,
+        }
+    }
+}
+
+/// Writes `grid` as a binary PPM image, with `colour` choosing the colour of each point, whether
+/// it is occupied or not.
+pub fn write_ppm<T>(
+    out: &mut impl Write,
+    grid: &impl Cells<T>,
+    options: &ImageOptions,
+    colour: impl Fn(Point, Option<&T>) -> Rgb,
+) -> io::Result<()> {
+    write_pnm(out, "P6", grid, options, |p, cell, row| {
+        row.extend(colour(p, cell))
+    })
+}
+
+/// Writes `grid` as a binary PGM image, with `grey` choosing the brightness of each point.
+pub fn write_pgm<T>(
+    out: &mut impl Write,
+    grid: &impl Cells<T>,
+    options: &ImageOptions,
+    grey: impl Fn(Point, Option<&T>) -> u8,
+) -> io::Result<()> {
+    write_pnm(out, "P5", grid, options, |p, cell, row| {
+        row.push(grey(p, cell))
+    })
+}
+
+/// Writes a netpbm image with the header `magic`, where `pixel` appends the samples of a point.
+fn write_pnm<T>(
+    out: &mut impl Write,
+    magic: &str,
+    grid: &impl Cells<T>,
+    options: &ImageOptions,
+    pixel: impl Fn(Point, Option<&T>, &mut Vec<u8>),
+) -> io::Result<()> {
+    assert!(options.scale > 0, "the scale of an image must be positive");
+
+    let Some(area) = options.viewport.or_else(|| grid.bounds()) else {
+        return writeln!(out, "{}\n0 0\n255", magic);
+    };
+
+    let scale = options.scale;
+    writeln!(
+        out,
+        "{}\n{} {}\n255",
+        magic,
+        area.width() * scale,
+        area.height() * scale
+    )?;
+
+    let mut row = vec![];
+
+    for y in area.min.y..=area.max.y {
+        row.clear();
+
+        for x in area.min.x..=area.max.x {
+            let p = Point::new(x, y);
+            let start = row.len();
+
+            pixel(p, grid.cell(p), &mut row);
+
+            let samples = row.len() - start;
+            for _ in 1..scale {
+                row.extend_from_within(start..start + samples);
+            }
+        }
+
+        for _ in 0..scale {
+            out.write_all(&row)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes numbered PPM images of a simulation to a directory, e.g. to make a video with
+/// `ffmpeg -i frame_%05d.ppm`.
+pub struct FrameRecorder {
+    dir: PathBuf,
+    options: ImageOptions,
+    frames: usize,
+}
+
+impl FrameRecorder {
+    /// Records into `dir`, creating it if needed.
+    ///
+    /// Videos need frames of a single size, so the viewport should be set when the bounds of the
+    /// grid change during the simulation.
+    pub fn new(dir: impl Into<PathBuf>, options: ImageOptions) -> Result<Self> {
+        let dir = dir.into();
+
+        std::fs::create_dir_all(&dir).context(format!("Failed to create `{}`", dir.display()))?;
+
+        Ok(Self {
+            dir,
+            options,
+            frames: 0,
+        })
+    }
+
+    /// Writes the next frame, returning its path.
+    pub fn record<T>(
+        &mut self,
+        grid: &impl Cells<T>,
+        colour: impl Fn(Point, Option<&T>) -> Rgb,
+    ) -> Result<PathBuf> {
+        let path = self.dir.join(format!("frame_{:05}.ppm", self.frames));
+
+        let mut out = io::BufWriter::new(
+            std::fs::File::create(&path)
+                .context(format!("Failed to create `{}`", path.display()))?,
+        );
+        write_ppm(&mut out, grid, &self.options, colour)
+            .and_then(|_| out.flush())
+            .context(format!("Failed to write `{}`", path.display()))?;
+
+        self.frames += 1;
+
+        Ok(path)
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[test]
+fn test_write_images() {
+    use crate::grid::Grid;
+
+    let grid = Grid::new(vec![vec![0u8, 255], vec![128, 0]]).unwrap();
+
+    let mut out = vec![];
+    write_pgm(&mut out, &grid, &ImageOptions::default(), |_, v| {
+        *v.unwrap()
+    })
+    .unwrap();
+    assert_eq!(out, b"P5\n2 2\n255\n\x00\xff\x80\x00");
+
+    let options = ImageOptions {
+        viewport: Some(Rect::new(Point::new(1, 0), Point::new(2, 0))),
+        scale: 2,
+    };
+    let mut out = vec![];
+    write_ppm(&mut out, &grid, &options, |_, v| match v {
+        Some(&v) => [v, 0, 0],
+        None => [0, 0, 9],
+    })
+    .unwrap();
+
+    let pixels: &[u8] = &[255, 0, 0, 255, 0, 0, 0, 0, 9, 0, 0, 9];
+    assert_eq!(out, [b"P6\n4 2\n255\n", pixels, pixels].concat());
+}
+
+#[test]
+fn test_frame_recorder() {
+    use crate::grid::InfiniteGrid;
+
+    let dir = std::env::temp_dir().join(format!("aoc2022-frames-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut recorder = FrameRecorder::new(&dir, ImageOptions::default()).unwrap();
+    let mut grid = InfiniteGrid::new();
+
+    for x in 0..3 {
+        grid.insert(Point::new(x, -x), ());
+        recorder
+            .record(&grid, |_, v| [v.map_or(0, |_| 255); 3])
+            .unwrap();
+    }
+
+    assert_eq!(recorder.frames(), 3);
+    assert!(std::fs::read(dir.join("frame_00002.ppm"))
+        .unwrap()
+        .starts_with(b"P6\n3 3\n255\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
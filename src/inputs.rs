@@ -0,0 +1,381 @@
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+
+/// Something that can GET a page of the puzzle site on behalf of a logged in user.
+pub trait Fetcher {
+    fn get(&self, url: &str, session: &Session) -> Result<String>;
+}
+
+/// Fetches over HTTP(S).
+#[derive(Debug, Default)]
+pub struct HttpFetcher;
+
+impl Fetcher for HttpFetcher {
+    fn get(&self, url: &str, session: &Session) -> Result<String> {
+        let response = ureq::get(url)
+            .set("Cookie", &format!("session={}", session.0))
+            .set("User-Agent", "github.com/omerbenamram/aoc2022")
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(code, _) => {
+                    anyhow::anyhow!("`{}` returned status {}", url, code)
+                }
+                e => anyhow::Error::new(e).context(format!("Failed to fetch `{}`", url)),
+            })?;
+
+        response
+            .into_string()
+            .context(format!("Failed to read the response of `{}`", url))
+    }
+}
+
+/// The value of the `session` cookie of a logged in user.
+#[derive(Clone)]
+pub struct Session(String);
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Session(..)")
+    }
+}
+
+impl Session {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into().trim().to_string())
+    }
+
+    /// Reads the token from `$AOC_SESSION`, the file named by `$AOC_SESSION_FILE`,
+    /// or `~/.adventofcode.session`, in that order.
+    pub fn from_env() -> Result<Self> {
+        if let Ok(token) = std::env::var("AOC_SESSION") {
+            return Ok(Self::new(token));
+        }
+
+        let path = match std::env::var_os("AOC_SESSION_FILE") {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let home = std::env::var_os("HOME").context(
+                    "No session token: set `AOC_SESSION` or `AOC_SESSION_FILE`, or create `~/.adventofcode.session`",
+                )?;
+                Path::new(&home).join(".adventofcode.session")
+            }
+        };
+
+        let token = std::fs::read_to_string(&path).context(format!(
+            "No session token: set `AOC_SESSION`, or write it to `{}`",
+            path.display()
+        ))?;
+
+        Ok(Self::new(token))
+    }
+}
+
+/// FNV-1a hash of the input, to notice local edits. Not meant to be cryptographically secure.
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+/// Recorded next to every fetched input as `dayN.meta.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    pub day: u8,
+    /// Seconds since the unix epoch.
+    pub fetched_at: u64,
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was downloaded and stored.
+    Downloaded,
+    /// An unmodified copy was already stored, nothing was downloaded.
+    Cached,
+}
+
+impl Display for Fetched {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fetched::Downloaded => f.write_str("downloaded"),
+            Fetched::Cached => f.write_str("already up to date"),
+        }
+    }
+}
+
+/// Stores puzzle inputs under a directory (normally `input/`), fetching missing ones.
+pub struct InputCache<F = HttpFetcher> {
+    dir: PathBuf,
+    base_url: String,
+    fetcher: F,
+}
+
+impl InputCache<HttpFetcher> {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self::with_fetcher(dir, DEFAULT_BASE_URL, HttpFetcher)
+    }
+}
+
+impl<F: Fetcher> InputCache<F> {
+    pub fn with_fetcher(dir: impl Into<PathBuf>, base_url: impl Into<String>, fetcher: F) -> Self {
+        Self {
+            dir: dir.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            fetcher,
+        }
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}", day))
+    }
+
+    pub fn metadata_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}.meta.toml", day))
+    }
+
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day)
+    }
+
+    pub fn metadata(&self, day: u8) -> Result<Option<Metadata>> {
+        let path = self.metadata_path(day);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .context(format!("Failed to read `{}`", path.display()))?;
+
+        toml::from_str(&contents)
+            .context(format!("Failed to parse `{}`", path.display()))
+            .map(Some)
+    }
+
+    /// Makes sure the input of `day` is stored, downloading it if needed.
+    ///
+    /// Refuses to replace an input which was edited since it was fetched, or which was not fetched
+    /// by the cache at all, unless `force` is set. An empty input without metadata, as created by
+    /// `aoc new`, was not fetched yet and is replaced.
+    pub fn fetch(&self, day: u8, session: &Session, force: bool) -> Result<Fetched> {
+        let path = self.input_path(day);
+
+        if path.exists() && !force {
+            let stored = std::fs::read_to_string(&path)
+                .context(format!("Failed to read `{}`", path.display()))?;
+
+            match self.metadata(day)? {
+                Some(metadata) if metadata.checksum == checksum(&stored) => {
+                    return Ok(Fetched::Cached)
+                }
+                Some(_) => bail!(
+                    "`{}` was edited since it was fetched, use --force to replace it",
+                    path.display()
+                ),
+                None if stored.is_empty() => {}
+                None => bail!(
+                    "`{}` already exists but was not fetched by `aoc download`, use --force to replace it",
+                    path.display()
+                ),
+            }
+        }
+
+        let input = self.fetcher.get(&self.url(day), session)?;
+
+        let metadata = Metadata {
+            day,
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .context("System clock is before the unix epoch")?
+                .as_secs(),
+            checksum: checksum(&input),
+        };
+
+        std::fs::create_dir_all(&self.dir)
+            .context(format!("Failed to create `{}`", self.dir.display()))?;
+        std::fs::write(&path, &input).context(format!("Failed to write `{}`", path.display()))?;
+
+        let metadata_path = self.metadata_path(day);
+        std::fs::write(&metadata_path, toml::to_string_pretty(&metadata)?)
+            .context(format!("Failed to write `{}`", metadata_path.display()))?;
+
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::*;
+
+    /// A stand-in for the puzzle site, answering every request with `status` and `body`.
+    ///
+    /// Returns the base url, and a receiver for the request line and cookie of every request.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut cookie = String::new();
+                reader.read_line(&mut request).unwrap();
+
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_string();
+                    }
+                }
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+
+                if tx.send((request.trim().to_string(), cookie)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        (base_url, rx)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2022-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (base_url, requests) = mock_server(200, "1000\n2000\n");
+        let dir = temp_dir("fetch");
+        let cache = InputCache::with_fetcher(&dir, format!("{}/", base_url), HttpFetcher);
+        let session = Session::new("secret\n");
+
+        assert_eq!(
+            cache.fetch(1, &session, false).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(
+            requests.recv().unwrap(),
+            (
+                "GET /2022/day/1/input HTTP/1.1".to_string(),
+                "session=secret".to_string()
+            )
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day1")).unwrap(),
+            "1000\n2000\n"
+        );
+        let metadata = cache.metadata(1).unwrap().unwrap();
+        assert_eq!(metadata.day, 1);
+        assert_eq!(metadata.checksum, checksum("1000\n2000\n"));
+
+        // An unmodified input is not fetched again.
+        assert_eq!(cache.fetch(1, &session, false).unwrap(), Fetched::Cached);
+        assert!(requests.try_recv().is_err());
+
+        std::fs::write(dir.join("day1"), "1000\n").unwrap();
+        let e = cache.fetch(1, &session, false).unwrap_err();
+        assert!(e.to_string().contains("was edited"), "{}", e);
+
+        assert_eq!(cache.fetch(1, &session, true).unwrap(), Fetched::Downloaded);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day1")).unwrap(),
+            "1000\n2000\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, _requests) = mock_server(404, "Not found");
+        let dir = temp_dir("fetch-errors");
+        let cache = InputCache::with_fetcher(&dir, &base_url, HttpFetcher);
+        let session = Session::new("secret");
+
+        let e = cache.fetch(2, &session, false).unwrap_err();
+        assert!(e.to_string().ends_with("returned status 404"), "{}", e);
+        assert!(!dir.join("day2").exists());
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day2"), "A Y\n").unwrap();
+        let e = cache.fetch(2, &session, false).unwrap_err();
+        assert!(e.to_string().contains("was not fetched"), "{}", e);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_download_new_day() {
+        let (base_url, _requests) = mock_server(200, "1\n");
+        let root = temp_dir("download-new-day");
+        std::fs::create_dir_all(root.join("src/days")).unwrap();
+        std::fs::write(root.join("src/days/mod.rs"), include_str!("days/mod.rs")).unwrap();
+
+        crate::scaffold::new_day(&root, 12).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("input/day12")).unwrap(),
+            ""
+        );
+
+        let cache = InputCache::with_fetcher(root.join("input"), &base_url, HttpFetcher);
+        let session = Session::new("secret");
+        assert_eq!(
+            cache.fetch(12, &session, false).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("input/day12")).unwrap(),
+            "1\n"
+        );
+
+        // Only an empty input is taken for one which was not fetched yet.
+        std::fs::write(root.join("input/day13"), "2\n").unwrap();
+        let e = cache.fetch(13, &session, false).unwrap_err();
+        assert!(e.to_string().contains("was not fetched by `aoc download`"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_ne!(checksum("1000\n"), checksum("1000\n\n"));
+    }
+}
@@ -1,9 +1,22 @@
-use std::{ops::RangeInclusive, time::Duration};
-
-use anyhow::Context;
-use itertools::Itertools;
+use std::time::Duration;
 
+pub mod answer;
+pub mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod days;
 pub mod grid;
+pub mod image;
+pub mod inputs;
+pub mod parse;
+pub mod regions;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod summary;
 
 pub fn timed<R, F>(f: F) -> (R, Duration)
 where
@@ -15,17 +28,6 @@ where
     (result, t0.elapsed())
 }
 
-/// Parses input of the form `\d-\d` to an inclusive range.
-pub fn range_inclusive(i: &str) -> anyhow::Result<RangeInclusive<i32>> {
-    let (s1, e1) = i
-        .split('-')
-        .map(|number| number.parse::<i32>())
-        .collect_tuple()
-        .context(r#"Expected input of the form `\d-\d`"#)?;
-
-    Ok(s1?..=e1?)
-}
-
 #[macro_export]
 macro_rules! regex {
     ($re:literal $(,)?) => {{
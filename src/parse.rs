@@ -0,0 +1,526 @@
+//! Line-oriented input parsing.
+//!
+//! Small nom combinators ([`int`], [`list`], [`range`], [`key_value`], ...) are driven by [`Span`],
+//! which splits the puzzle input into lines, blank-line-separated blocks, records and grids, and
+//! reports failures as a [`ParseError`] located in the original input.
+
+use std::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::{char, digit1, one_of, space0, space1},
+    combinator::{all_consuming, map, map_res, opt, recognize, rest},
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair},
+};
+
+use crate::grid::Grid;
+
+pub type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// A parse failure pointing at a location in the puzzle input.
+///
+/// Renders as the offending line with the bad token underlined:
+///
+/// ```text
+/// line 3, column 1: expected one of `R`, `L`, `U`, `D`
+///   |
+/// 3 | X 4
+///   | ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// Number of characters to underline, at least one.
+    pub len: usize,
+    /// The full line containing the error.
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error located at `fragment`, which must be a subslice of `input`.
+    ///
+    /// An empty `fragment` points right after the text preceding it, which is useful for reporting
+    /// missing tokens.
+    pub fn at(input: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let position = fragment.as_ptr() as usize;
+        debug_assert!(
+            (start..=start + input.len()).contains(&position),
+            "fragment should be a subslice of input"
+        );
+        let offset = position.saturating_sub(start).min(input.len());
+
+        let prefix = &input[..offset];
+        let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+        let snippet = input[line_start..].lines().next().unwrap_or("");
+
+        let column = input[line_start..offset].chars().count() + 1;
+        let len = fragment
+            .lines()
+            .next()
+            .map_or(0, |l| l.chars().count())
+            .max(1);
+
+        Self {
+            line: prefix.matches('\n').count() + 1,
+            column,
+            len,
+            snippet: snippet.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Converts a nom error produced while parsing a subslice of `input`.
+    pub fn from_nom(input: &str, e: nom::Err<VerboseError<&str>>) -> Self {
+        let e = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => {
+                return Self::at(input, &input[input.len()..], "more input");
+            }
+        };
+
+        // Point at the innermost failure, but describe it with the first context, which is the
+        // most specific one.
+        let fragment = e.errors.first().map_or(&input[input.len()..], |(f, _)| *f);
+        let kind = e
+            .errors
+            .iter()
+            .map(|(_, kind)| kind)
+            .find(|kind| matches!(kind, VerboseErrorKind::Context(_)))
+            .or_else(|| e.errors.first().map(|(_, kind)| kind));
+
+        let expected = match kind {
+            Some(VerboseErrorKind::Context(c)) => c.to_string(),
+            Some(VerboseErrorKind::Char(c)) => format!("`{}`", c),
+            Some(VerboseErrorKind::Nom(ErrorKind::Eof)) => "end of input".to_string(),
+            Some(VerboseErrorKind::Nom(kind)) => kind.description().to_lowercase(),
+            None => "valid input".to_string(),
+        };
+
+        Self::at(input, fragment, expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(
+            f,
+            "{:gutter$} | {:pad$}{}",
+            "",
+            "",
+            "^".repeat(self.len),
+            pad = self.column - 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A signed or unsigned integer, such as `-12`, `+3` or `42`.
+pub fn int<'a, T: FromStr>(s: &'a str) -> IResult<'a, T> {
+    context(
+        "an integer",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(s)
+}
+
+/// Skips spaces and tabs around `parser`.
+pub fn ws<'a, T>(
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    delimited(space0, parser, space0)
+}
+
+/// One or more `item`s separated by `sep`, with optional spaces around separators, e.g. `79, 98,60`.
+pub fn list<'a, T>(
+    sep: char,
+    item: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(ws(char(sep)), item)
+}
+
+/// One or more `item`s separated by spaces, e.g. ` 1   2   3 `.
+pub fn words<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    ws(separated_list1(space1, item))
+}
+
+/// An inclusive range of integers of the form `2-4`.
+pub fn range<'a, T: FromStr>(s: &'a str) -> IResult<'a, RangeInclusive<T>> {
+    context(
+        "a range like `2-4`",
+        map(separated_pair(int, char('-'), int), |(start, end)| {
+            start..=end
+        }),
+    )(s)
+}
+
+/// A `key: value` pair, with the value trimmed. The value may be empty, as in `Monkey 0:`.
+pub fn key_value(s: &str) -> IResult<'_, (&str, &str)> {
+    context(
+        "`key: value`",
+        separated_pair(take_till1(|c| c == ':'), char(':'), map(rest, str::trim)),
+    )(s)
+}
+
+/// A piece of the puzzle input, which remembers the whole input to locate errors.
+#[derive(Debug, Clone, Copy)]
+pub struct Span<'a> {
+    root: &'a str,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            root: input,
+            text: input,
+        }
+    }
+
+    fn sub(&self, text: &'a str) -> Self {
+        Self {
+            root: self.root,
+            text,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn trim(&self) -> Self {
+        self.sub(self.text.trim())
+    }
+
+    /// An error located at `fragment`, which must be a subslice of this span.
+    pub fn error(&self, fragment: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.root, fragment, expected)
+    }
+
+    /// An error located right after the end of this span, for missing input.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    /// Runs `parser` over the whole span, failing if any input is left over.
+    pub fn parse<T>(&self, parser: impl FnMut(&'a str) -> IResult<'a, T>) -> Result<T, ParseError> {
+        all_consuming(parser)(self.text)
+            .map(|(_, t)| t)
+            .map_err(|e| ParseError::from_nom(self.root, e))
+    }
+
+    /// The non-blank lines of this span.
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+
+        self.text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(move |line| span.sub(line))
+    }
+
+    /// Runs `parser` over every non-blank line.
+    pub fn parse_lines<T>(
+        &self,
+        mut parser: impl FnMut(&'a str) -> IResult<'a, T>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.lines().map(|line| line.parse(&mut parser)).collect()
+    }
+
+    /// Groups of consecutive non-blank lines, separated by one or more blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        let mut lines = self.text.lines().peekable();
+
+        std::iter::from_fn(move || {
+            while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+
+            let first = lines.next()?;
+            let mut last = first;
+
+            while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+                last = line;
+            }
+
+            let start = offset(span.text, first);
+            let end = offset(span.text, last) + last.len();
+
+            Some(span.sub(&span.text[start..end]))
+        })
+    }
+
+    /// Parses every non-blank line as a `key: value` pair.
+    pub fn record(&self) -> Result<Record<'a>, ParseError> {
+        let fields = self
+            .lines()
+            .map(|line| line.trim().parse(key_value))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Record {
+            span: *self,
+            fields,
+        })
+    }
+
+    /// Parses a character map, mapping every character with `cell`. See [`Grid::parse`].
+    pub fn char_grid<T, E: Display>(
+        &self,
+        cell: impl Fn(char) -> Result<T, E>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut lines = self.text.lines().collect::<Vec<_>>();
+
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        let first = lines
+            .iter()
+            .position(|line| !line.trim().is_empty())
+            .unwrap_or(lines.len());
+
+        let mut width = None;
+        let mut cells = vec![];
+
+        for line in &lines[first..] {
+            let mut row = 0;
+
+            for (i, c) in line.char_indices() {
+                if width == Some(row) {
+                    return Err(self.error(&line[i..], format!("a row of {} cells", row)));
+                }
+
+                let c =
+                    cell(c).map_err(|e| self.error(&line[i..i + c.len_utf8()], e.to_string()))?;
+                cells.push(c);
+                row += 1;
+            }
+
+            match width {
+                Some(width) if row < width => {
+                    return Err(self.error(&line[line.len()..], format!("a row of {} cells", width)))
+                }
+                _ => width = Some(row),
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => {
+                Ok(Grid::from_vec(width, cells).expect("every row has `width` cells"))
+            }
+            _ => Ok(Grid::new(vec![]).expect("an empty grid is valid")),
+        }
+    }
+}
+
+/// Byte offset of `inner` within `outer`.
+fn offset(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// The `key: value` lines of a block, see [`Span::record`].
+#[derive(Debug, Clone)]
+pub struct Record<'a> {
+    span: Span<'a>,
+    fields: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Record<'a> {
+    pub fn keys(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.fields.iter().map(|(key, _)| *key)
+    }
+
+    pub fn get(&self, key: &str) -> Result<Span<'a>, ParseError> {
+        self.fields
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| self.span.sub(value))
+            .ok_or_else(|| self.span.error_at_end(format!("a `{}:` line", key)))
+    }
+
+    /// Runs `parser` over the whole value of `key`.
+    pub fn value<T>(
+        &self,
+        key: &str,
+        parser: impl FnMut(&'a str) -> IResult<'a, T>,
+    ) -> Result<T, ParseError> {
+        self.get(key)?.parse(parser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::sequence::preceded;
+
+    #[test]
+    fn test_at() {
+        let input = "R 4\nU 4\nX 3\n";
+        let line = input.lines().nth(2).unwrap();
+        let e = ParseError::at(input, &line[..1], "a direction");
+
+        assert_eq!((e.line, e.column, e.len), (3, 1, 1));
+        assert_eq!(e.snippet, "X 3");
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 1: expected a direction\n  |\n3 | X 3\n  | ^"
+        );
+
+        let e = ParseError::at(input, &line[2..], "a number");
+        assert_eq!((e.line, e.column, e.len), (3, 3, 1));
+
+        // Missing token at the end of the input.
+        let e = ParseError::at(input, &input[input.len()..], "a number");
+        assert_eq!((e.line, e.column, e.snippet.as_str()), (4, 1, ""));
+    }
+
+    #[test]
+    fn test_underline_width() {
+        let input = "1000\n20x0\n";
+        let token = input.lines().nth(1).unwrap();
+        let e = ParseError::at(input, token, "a number");
+
+        assert_eq!(e.to_string().lines().last().unwrap(), "  | ^^^^");
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let input = "Monkey 0:\n  Test: divisible by x";
+        let line = input.lines().nth(1).unwrap().trim();
+
+        let e = preceded(
+            nom::bytes::complete::tag("Test: divisible by "),
+            context("a divisor", digit1::<_, VerboseError<&str>>),
+        )(line)
+        .unwrap_err();
+
+        let e = ParseError::from_nom(input, e);
+        assert_eq!((e.line, e.column), (2, 22));
+        assert_eq!(e.expected, "a divisor");
+    }
+
+    #[test]
+    fn test_int() {
+        assert_eq!(int::<i32>("-12 rest"), Ok((" rest", -12)));
+        assert_eq!(int::<i32>("+3"), Ok(("", 3)));
+        assert_eq!(int::<u64>("42"), Ok(("", 42)));
+        assert!(int::<u64>("-42").is_err());
+        assert!(int::<i8>("300").is_err());
+    }
+
+    #[test]
+    fn test_list_and_range() {
+        let (_, items) = list(',', int::<i32>)("79, 98,60 ,1").unwrap();
+        assert_eq!(items, vec![79, 98, 60, 1]);
+
+        let (_, items) = words(int::<i32>)(" 1   2   3 ").unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+
+        assert_eq!(range::<i32>("2-4"), Ok(("", 2..=4)));
+        assert_eq!(
+            Span::new("2-4,-6-8")
+                .parse(separated_pair(range::<i32>, char(','), range))
+                .unwrap(),
+            (2..=4, -6..=8)
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "1\n2\n\n3x\n";
+        let e = Span::new(input).parse_lines(int::<i32>).unwrap_err();
+
+        assert_eq!((e.line, e.column), (4, 2));
+        assert_eq!(e.expected, "end of input");
+
+        assert_eq!(
+            Span::new("1\n 2\n\n3\n").parse_lines(ws(int::<i32>)),
+            Ok(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "\n1\n2\n\n  \n3\n\n";
+        let blocks = Span::new(input)
+            .blocks()
+            .map(|b| b.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(blocks, vec!["1\n2", "3"]);
+
+        let block = Span::new(input).blocks().nth(1).unwrap();
+        let e = block.parse(char::<_, VerboseError<&str>>('4')).unwrap_err();
+        assert_eq!(e.line, 6);
+    }
+
+    #[test]
+    fn test_record() {
+        let input = "Monkey 0:\n  Starting items: 79, 98\n  Test: divisible by 23\n";
+        let record = Span::new(input).record().unwrap();
+
+        assert_eq!(
+            record.keys().collect::<Vec<_>>(),
+            vec!["Monkey 0", "Starting items", "Test"]
+        );
+        assert_eq!(
+            record.value("Starting items", list(',', int::<i32>)),
+            Ok(vec![79, 98])
+        );
+
+        let e = record.get("Operation").unwrap_err();
+        assert_eq!(e.expected, "a `Operation:` line");
+
+        let e = record
+            .value(
+                "Test",
+                nom::sequence::preceded(nom::bytes::complete::tag("divisible by "), int::<u8>),
+            )
+            .unwrap();
+        assert_eq!(e, 23);
+
+        assert!(Span::new("no colon here").record().is_err());
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let digit = |c: char| c.to_digit(10).ok_or("a digit");
+
+        let grid = Span::new("\n  \n123\n456\n\n").char_grid(digit).unwrap();
+        assert_eq!(grid, Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap());
+
+        let e = Span::new("123\n4x6\n").char_grid(digit).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 2, "a digit"));
+
+        let e = Span::new("123\n45\n").char_grid(digit).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.expected, "a row of 3 cells");
+
+        let e = Span::new("123\n4567\n").char_grid(digit).unwrap_err();
+        assert_eq!((e.line, e.column, e.len), (2, 4, 1));
+
+        let e = Span::new("123\n\n456\n").char_grid(digit).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+
+        assert_eq!(Span::new("\n\n").char_grid(digit).unwrap().height(), 0);
+    }
+}
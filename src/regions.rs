@@ -0,0 +1,226 @@
+//! Regions of grids: flood fill and connected components.
+//!
+//! Both work on any [`Cells`] within explicit bounds, so they apply to an [`InfiniteGrid`] as well
+//! as to a [`Grid`], and can fill the empty space around the occupied cells.
+//!
+//! [`InfiniteGrid`]: crate::grid::InfiniteGrid
+
+use std::collections::HashSet;
+
+use crate::grid::{Cells, Grid, Point, Rect, ADJACENT, ORTHOGONAL};
+
+/// Which points count as touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Orthogonal neighbours only.
+    Four,
+    /// Orthogonal and diagonal neighbours.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [Point] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL,
+            Connectivity::Eight => &ADJACENT,
+        }
+    }
+}
+
+/// The points within `bounds` reachable from `seed` through points matching `include`, `seed`
+/// included. Empty if `seed` itself does not match.
+pub fn flood_fill<T>(
+    grid: &impl Cells<T>,
+    bounds: Rect,
+    seed: Point,
+    connectivity: Connectivity,
+    include: impl Fn(Point, Option<&T>) -> bool,
+) -> HashSet<Point> {
+    let mut filled = HashSet::new();
+
+    if !bounds.contains(seed) || !include(seed, grid.cell(seed)) {
+        return filled;
+    }
+
+    let mut stack = vec![seed];
+    filled.insert(seed);
+
+    while let Some(p) = stack.pop() {
+        for &d in connectivity.offsets() {
+            let n = p + d;
+
+            if bounds.contains(n) && !filled.contains(&n) && include(n, grid.cell(n)) {
+                filled.insert(n);
+                stack.push(n);
+            }
+        }
+    }
+
+    filled
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// The number of points in the component.
+    pub size: usize,
+    pub bounds: Rect,
+    /// Whether the component reaches the edge of the labelled area, i.e. is not enclosed.
+    pub touches_edge: bool,
+}
+
+/// The connected components of an area, numbered row by row in the order they are first seen.
+#[derive(Debug, Clone)]
+pub struct Components {
+    /// The component of every point of the area, relative to `area.min`.
+    pub labels: Grid<Option<usize>>,
+    pub components: Vec<Component>,
+    pub area: Rect,
+}
+
+impl Components {
+    /// The component of `p`, if it belongs to one.
+    pub fn label(&self, p: Point) -> Option<usize> {
+        *self.labels.get_point(p - self.area.min)?
+    }
+
+    /// The components which do not reach the edge of the area, with their labels.
+    pub fn enclosed(&self) -> impl Iterator<Item = (usize, &Component)> {
+        self.components
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.touches_edge)
+    }
+
+    /// The components which reach the edge of the area, with their labels.
+    pub fn touching_edge(&self) -> impl Iterator<Item = (usize, &Component)> {
+        self.components
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.touches_edge)
+    }
+}
+
+/// Labels the connected components of the points in `area` matching `include`.
+pub fn components<T>(
+    grid: &impl Cells<T>,
+    area: Rect,
+    connectivity: Connectivity,
+    include: impl Fn(Point, Option<&T>) -> bool,
+) -> Components {
+    let mut labels = Grid::filled(area.width(), area.height(), None);
+    let mut components = vec![];
+
+    let on_edge = |q: &Point| {
+        q.x == area.min.x || q.x == area.max.x || q.y == area.min.y || q.y == area.max.y
+    };
+
+    for p in area.points() {
+        if labels[p - area.min].is_some() || !include(p, grid.cell(p)) {
+            continue;
+        }
+
+        let label = Some(components.len());
+        let filled = flood_fill(grid, area, p, connectivity, &include);
+        for q in &filled {
+            *labels
+                .get_point_mut(*q - area.min)
+                .expect("filled within the area") = label;
+        }
+
+        components.push(Component {
+            size: filled.len(),
+            bounds: filled
+                .iter()
+                .fold(Rect::point(p), |bounds, &q| bounds.extend(q)),
+            touches_edge: filled.iter().any(on_edge),
+        });
+    }
+
+    Components {
+        labels,
+        components,
+        area,
+    }
+}
+
+#[test]
+fn test_flood_fill() {
+    let grid = Grid::parse(
+        "
+#####.
+#..#..
+#..###
+#####.
+",
+        Ok::<char, &str>,
+    )
+    .unwrap();
+    let bounds = Rect::new(Point::ZERO, Point::new(5, 3));
+    let open = |_, c: Option<&char>| c == Some(&'.');
+
+    let filled = flood_fill(&grid, bounds, Point::new(1, 1), Connectivity::Four, open);
+    assert_eq!(filled.len(), 4);
+
+    let filled = flood_fill(&grid, bounds, Point::new(0, 0), Connectivity::Four, open);
+    assert!(filled.is_empty());
+
+    // The walls are one connected piece either way.
+    let wall = |_, c: Option<&char>| c == Some(&'#');
+    let filled = flood_fill(&grid, bounds, Point::ZERO, Connectivity::Four, wall);
+    assert_eq!(filled.len(), 16);
+
+    let components = components(&grid, bounds, Connectivity::Four, open);
+    assert_eq!(components.components.len(), 3);
+    assert_eq!(components.label(Point::new(2, 2)), Some(1));
+    assert_eq!(components.label(Point::new(0, 0)), None);
+
+    let enclosed = components.enclosed().collect::<Vec<_>>();
+    assert_eq!(
+        enclosed,
+        [(
+            1,
+            &Component {
+                size: 4,
+                bounds: Rect::new(Point::new(1, 1), Point::new(2, 2)),
+                touches_edge: false,
+            }
+        )]
+    );
+    assert_eq!(
+        components
+            .touching_edge()
+            .map(|(_, c)| c.size)
+            .collect::<Vec<_>>(),
+        [3, 1]
+    );
+}
+
+#[test]
+fn test_components_infinite_grid() {
+    use crate::grid::InfiniteGrid;
+
+    // A diamond of walls around an empty (0, 0).
+    let mut grid = InfiniteGrid::new();
+    for p in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+        grid.insert(p.into(), ());
+    }
+
+    let area = grid.dimensions().unwrap();
+    let empty = |_, c: Option<&()>| c.is_none();
+
+    let four = components(&grid, area, Connectivity::Four, empty);
+    assert_eq!(four.components.len(), 5);
+    assert_eq!(
+        four.enclosed().map(|(label, _)| label).collect::<Vec<_>>(),
+        [2]
+    );
+
+    // Diagonal steps leak through the corners of the diamond.
+    let eight = components(&grid, area, Connectivity::Eight, empty);
+    assert_eq!(eight.components.len(), 1);
+    assert_eq!(eight.components[0].size, 5);
+
+    let walls = components(&grid, area, Connectivity::Eight, |_, c| c.is_some());
+    assert_eq!(walls.components.len(), 1);
+    assert!(walls.components[0].touches_edge);
+}
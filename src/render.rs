@@ -0,0 +1,145 @@
+use std::fmt::{self, Write};
+
+use crate::grid::{Cells, Point, Rect};
+
+/// Which way `y` grows on screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// Row `y + 1` is drawn below row `y`, like a [`crate::grid::Grid`].
+    #[default]
+    YDown,
+    /// Row `y + 1` is drawn above row `y`, like a plot.
+    YUp,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// The area to draw. Defaults to the bounding box of the grid.
+    pub viewport: Option<Rect>,
+    pub orientation: Orientation,
+    /// Label rows and columns with their coordinates.
+    pub axes: bool,
+}
+
+/// Draws `grid` as text, one line per row, with `glyph` choosing the character of each point,
+/// whether it is occupied or not.
+///
+/// `overlays` are drawn over the grid, later ones over earlier ones, e.g. to mark moving parts
+/// which are not stored in the grid. Points outside of the viewport are left out.
+pub fn render<T>(
+    out: &mut impl Write,
+    grid: &impl Cells<T>,
+    options: &RenderOptions,
+    glyph: impl Fn(Point, Option<&T>) -> char,
+    overlays: &[(Point, char)],
+) -> fmt::Result {
+    let Some(Rect { min, max }) = options.viewport.or_else(|| grid.bounds()) else {
+        return Ok(());
+    };
+
+    let rows: Box<dyn Iterator<Item = i32>> = match options.orientation {
+        Orientation::YDown => Box::new(min.y..=max.y),
+        Orientation::YUp => Box::new((min.y..=max.y).rev()),
+    };
+
+    let label_width = |from: i32, to: i32| from.to_string().len().max(to.to_string().len());
+    let row_labels = if options.axes {
+        label_width(min.y, max.y) + 1
+    } else {
+        0
+    };
+
+    if options.axes {
+        // Column labels are written top to bottom, one character per line.
+        let width = label_width(min.x, max.x);
+        let labels = (min.x..=max.x)
+            .map(|x| format!("{:>width$}", x).chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        for line in 0..width {
+            write!(out, "{:row_labels$}", "")?;
+
+            for label in &labels {
+                out.write_char(label[line])?;
+            }
+
+            writeln!(out)?;
+        }
+    }
+
+    for y in rows {
+        if options.axes {
+            write!(out, "{:>1$} ", y, row_labels - 1)?;
+        }
+
+        for x in min.x..=max.x {
+            let p = Point::new(x, y);
+
+            let c = overlays
+                .iter()
+                .rev()
+                .find(|(o, _)| *o == p)
+                .map_or_else(|| glyph(p, grid.cell(p)), |&(_, c)| c);
+
+            out.write_char(c)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_render() {
+    use crate::grid::{Grid, InfiniteGrid};
+
+    let mut grid = InfiniteGrid::new();
+
+    for (x, y) in [(-1, 0), (0, 0), (1, 1), (10, 2)] {
+        grid.insert(Point::new(x, y), ());
+    }
+
+    let glyph = |_, v: Option<&()>| if v.is_some() { '#' } else { '.' };
+
+    let mut out = String::new();
+    render(
+        &mut out,
+        &grid,
+        &RenderOptions::default(),
+        glyph,
+        &[(Point::new(0, 0), 's')],
+    )
+    .unwrap();
+
+    assert_eq!(out, "#s..........\n..#.........\n...........#\n");
+
+    let options = RenderOptions {
+        viewport: Some(Rect::new(Point::new(-1, -1), Point::new(1, 1))),
+        orientation: Orientation::YUp,
+        axes: true,
+    };
+
+    let mut out = String::new();
+    let overlays = [(Point::new(0, 1), 'T'), (Point::new(0, 1), 'H')];
+    render(&mut out, &grid, &options, glyph, &overlays).unwrap();
+
+    assert_eq!(out, "   -  \n   101\n 1 .H#\n 0 ##.\n-1 ...\n");
+
+    let mut out = String::new();
+    render(&mut out, &InfiniteGrid::<()>::new(), &options, glyph, &[]).unwrap();
+    assert_eq!(out.lines().count(), 5);
+
+    // Dense grids draw the same way.
+    let dense = Grid::parse("#.\n.#", Ok::<char, &str>).unwrap();
+    let mut out = String::new();
+    render(
+        &mut out,
+        &dense,
+        &RenderOptions::default(),
+        |_, c| *c.unwrap(),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(out, "#.\n.#\n");
+}
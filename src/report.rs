@@ -0,0 +1,179 @@
+use std::{
+    io::Write,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::{answer::Answer, inputs::checksum, runner::DayRun};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// One JSON object per line.
+    Jsonl,
+    Csv,
+}
+
+/// A single part of a single run, flattened for dashboards.
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub revision: Option<String>,
+    pub day: u8,
+    pub part: u8,
+    pub success: bool,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub parse_ns: u64,
+    /// Unset when the input could not be parsed.
+    pub solve_ns: Option<u64>,
+    /// Unset when the input could not be read.
+    pub input_checksum: Option<String>,
+}
+
+/// The revision of the working tree, with a `-dirty` suffix if it has local changes.
+pub fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// One record per part of `run`, which solved `input`, or failed before it could be read.
+pub fn records(
+    run: &DayRun,
+    input: Option<&str>,
+    revision: Option<&str>,
+    timestamp: u64,
+) -> Vec<Record> {
+    let record = |part, result: Result<&Answer, String>, solve_ns| Record {
+        timestamp,
+        revision: revision.map(String::from),
+        day: run.day,
+        part,
+        success: result.is_ok(),
+        answer: result.as_ref().ok().map(|&answer| answer.clone()),
+        error: result.err(),
+        parse_ns: run.parse_took.as_nanos() as u64,
+        solve_ns,
+        input_checksum: input.map(checksum),
+    };
+
+    match &run.parts {
+        Ok(parts) => parts
+            .iter()
+            .map(|part| {
+                record(
+                    part.part,
+                    part.answer.as_ref().map_err(|e| format!("{:#}", e)),
+                    Some(part.took.as_nanos() as u64),
+                )
+            })
+            .collect(),
+        Err(e) => (1..=2)
+            .map(|part| record(part, Err(format!("{:#}", e)), None))
+            .collect(),
+    }
+}
+
+pub fn write(format: Format, records: &[Record], mut writer: impl Write) -> Result<()> {
+    match format {
+        Format::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut writer, record)?;
+                writeln!(writer)?;
+            }
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(writer);
+
+            for record in records {
+                writer.serialize(record)?;
+            }
+
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_records() {
+    let input = "1000\n2000\n\n4000";
+    let run = crate::runner::solve(&crate::days::day1::Day1, input);
+    let records = records(&run, Some(input), Some("abc123"), 1670000000);
+
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.success && r.solve_ns.is_some()));
+    assert_eq!(records[0].answer, Some(Answer::from(4000)));
+    assert_eq!(records[1].part, 2);
+
+    let mut jsonl = vec![];
+    write(Format::Jsonl, &records, &mut jsonl).unwrap();
+    let jsonl = String::from_utf8(jsonl).unwrap();
+    let first: serde_json::Value = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+
+    assert_eq!(jsonl.lines().count(), 2);
+    assert_eq!(first["answer"], 4000);
+    assert_eq!(first["revision"], "abc123");
+    assert_eq!(first["error"], serde_json::Value::Null);
+
+    let mut csv = vec![];
+    write(Format::Csv, &records, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines = csv.lines();
+
+    assert_eq!(
+        lines.next().unwrap(),
+        "timestamp,revision,day,part,success,answer,error,parse_ns,solve_ns,input_checksum"
+    );
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("1670000000,abc123,1,1,true,4000,,"));
+}
+
+#[test]
+fn test_records_parse_error() {
+    let input = "1000\nlots\n";
+    let run = crate::runner::solve(&crate::days::day1::Day1, input);
+    let records = records(&run, Some(input), None, 0);
+
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| !r.success && r.solve_ns.is_none()));
+    assert!(records[0]
+        .error
+        .as_deref()
+        .unwrap()
+        .starts_with("Failed to parse input: line 2, column 1"));
+}
+
+#[test]
+fn test_records_missing_input() {
+    let run = DayRun::failed(1, anyhow::anyhow!("No input for day 1"));
+    let records = records(&run, None, None, 0);
+
+    assert_eq!(records.len(), 2);
+    assert!(records
+        .iter()
+        .all(|r| !r.success && r.input_checksum.is_none()));
+    assert_eq!(records[1].error.as_deref(), Some("No input for day 1"));
+}
@@ -0,0 +1,298 @@
+use std::{
+    io::{self, IsTerminal},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+
+use crate::{answer::Answer, days, solution::DynSolution, timed};
+
+/// The outcome of solving one part.
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub took: Duration,
+}
+
+/// The outcome of parsing and solving both parts of a day.
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: u8,
+    pub parse_took: Duration,
+    /// Both parts, unless parsing failed.
+    pub parts: Result<[PartRun; 2]>,
+}
+
+impl DayRun {
+    /// A run which failed before the input could be parsed, e.g. because it is missing.
+    pub fn failed(day: u8, error: anyhow::Error) -> Self {
+        Self {
+            day,
+            parse_took: Duration::ZERO,
+            parts: Err(error),
+        }
+    }
+}
+
+/// Turns a panic in `f` into an error.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown payload".to_string());
+
+        Err(anyhow!("Panicked: {}", message))
+    })
+}
+
+/// Parses `input` and solves both parts, timing every step.
+///
+/// A panic while parsing or solving is reported as an error of that step.
+pub fn solve(solution: &dyn DynSolution, input: &str) -> DayRun {
+    let (parsed, parse_took) = timed(|| catch_panic(|| solution.parse(input)));
+
+    let parts = parsed.context("Failed to parse input").map(|parsed| {
+        let (answer, took) = timed(|| catch_panic(|| solution.part1(parsed.as_ref())));
+        let part1 = PartRun {
+            part: 1,
+            answer,
+            took,
+        };
+
+        let (answer, took) = timed(|| catch_panic(|| solution.part2(parsed.as_ref())));
+        let part2 = PartRun {
+            part: 2,
+            answer,
+            took,
+        };
+
+        [part1, part2]
+    });
+
+    DayRun {
+        day: solution.day(),
+        parse_took,
+        parts,
+    }
+}
+
+/// Solves every day with its input on up to `threads` worker threads.
+///
+/// The runs are returned in the order of `jobs`.
+pub fn solve_all(jobs: &[(&dyn DynSolution, String)], threads: usize) -> Vec<DayRun> {
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new((0..jobs.len()).map(|_| None).collect::<Vec<_>>());
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some((solution, input)) = jobs.get(i) else {
+                    break;
+                };
+
+                let run = solve(*solution, input);
+                runs.lock()
+                    .expect("no worker panics while holding the lock")[i] = Some(run);
+            });
+        }
+    });
+
+    runs.into_inner()
+        .expect("no worker panics while holding the lock")
+        .into_iter()
+        .map(|run| run.expect("every job is solved"))
+        .collect()
+}
+
+/// Parses `input` and runs both parts, printing the answers and timings.
+pub fn run(solution: &dyn DynSolution, input: &str) -> Result<()> {
+    print(solve(solution, input))
+}
+
+/// Prints the answers and timings of `run`, failing with the first error.
+pub fn print(run: DayRun) -> Result<()> {
+    let parts = run.parts?;
+    println!("Parsing input took: {:?}", run.parse_took);
+
+    for part in parts {
+        print_answer(part.part, &part.answer?, part.took);
+    }
+
+    Ok(())
+}
+
+fn print_answer(part: u8, answer: &Answer, took: Duration) {
+    if answer.is_multiline() {
+        println!("Part {} answer:\n{}took: {:?}", part, answer, took);
+    } else {
+        println!("Part {} answer: `{}`; took: {:?}", part, answer, took);
+    }
+}
+
+pub fn input_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("input/day{}", day))
+}
+
+pub fn example_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("input/day{}_test", day))
+}
+
+fn read_file(day: u8, path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            bail!(
+                "No input for day {}: expected it at `{}`",
+                day,
+                path.display()
+            )
+        }
+        result => result.context(format!("Failed to read input from `{}`", path.display())),
+    }
+}
+
+/// Reads the puzzle input for `day` from `input/dayN`.
+pub fn read_input(day: u8) -> Result<String> {
+    read_file(day, &input_path(day))
+}
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct InputArgs {
+    /// Read the input from this file instead of `input/dayN`.
+    #[arg(long, conflicts_with = "example")]
+    pub input: Option<PathBuf>,
+
+    /// Use the example input from `input/dayN_test`.
+    #[arg(long)]
+    pub example: bool,
+}
+
+impl InputArgs {
+    /// The file to read for `day`.
+    pub fn path(&self, day: u8) -> PathBuf {
+        match &self.input {
+            Some(path) => path.clone(),
+            None if self.example => example_path(day),
+            None => input_path(day),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String> {
+        read_file(day, &self.path(day))
+    }
+
+    /// Like [`InputArgs::read`], but prefers stdin when input is piped in and no file was given.
+    pub fn read_or_stdin(&self, day: u8) -> Result<String> {
+        if self.input.is_none() && !self.example && !io::stdin().is_terminal() {
+            return std::io::read_to_string(io::stdin()).context("Failed to read input from stdin");
+        }
+
+        self.read(day)
+    }
+}
+
+#[derive(Parser)]
+struct DayCli {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Entry point for the per-day binaries.
+///
+/// Input is read from `--input`, `--example`, stdin when piped, or `input/dayN` otherwise.
+pub fn main(day: u8) -> Result<()> {
+    env_logger::init();
+
+    let cli = DayCli::parse();
+    let solution = days::get(day).context(format!("Day {} is not registered", day))?;
+    let input = cli.input.read_or_stdin(day)?;
+
+    run(solution, &input)
+}
+
+#[test]
+fn test_solve_all() {
+    struct Panics;
+
+    impl crate::solution::Solution for Panics {
+        const DAY: u8 = 25;
+
+        type Input = ();
+        type Output1 = i32;
+        type Output2 = i32;
+
+        fn parse_input(_input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Result<i32> {
+            Ok(1)
+        }
+
+        fn part2(_input: &()) -> Result<i32> {
+            panic!("out of coffee")
+        }
+    }
+
+    let jobs: Vec<(&dyn DynSolution, String)> = vec![
+        (&Panics, String::new()),
+        (&days::day1::Day1, "1\n2\n\n4".to_string()),
+        (&days::day1::Day1, "one".to_string()),
+    ];
+
+    let runs = solve_all(&jobs, 2);
+
+    assert_eq!(runs.iter().map(|r| r.day).collect::<Vec<_>>(), [25, 1, 1]);
+
+    let [part1, part2] = runs[0].parts.as_ref().unwrap();
+    assert_eq!(*part1.answer.as_ref().unwrap(), Answer::from(1));
+    assert_eq!(
+        part2.answer.as_ref().unwrap_err().to_string(),
+        "Panicked: out of coffee"
+    );
+
+    let [_, part2] = runs[1].parts.as_ref().unwrap();
+    assert_eq!(*part2.answer.as_ref().unwrap(), Answer::from(7));
+
+    assert!(runs[2].parts.is_err());
+}
+
+#[test]
+fn test_input_args() {
+    let args = InputArgs::default();
+    assert_eq!(args.path(5), PathBuf::from("input/day5"));
+
+    let args = InputArgs {
+        example: true,
+        ..Default::default()
+    };
+    assert_eq!(args.path(10), PathBuf::from("input/day10_test"));
+
+    let args = InputArgs {
+        input: Some(PathBuf::from("other")),
+        example: false,
+    };
+    assert_eq!(args.path(10), PathBuf::from("other"));
+
+    let e = InputArgs {
+        input: Some(PathBuf::from("input/does_not_exist")),
+        example: false,
+    }
+    .read(3)
+    .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "No input for day 3: expected it at `input/does_not_exist`"
+    );
+}
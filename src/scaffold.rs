@@ -0,0 +1,212 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+
+const TEMPLATE: &str = include_str!("../template.rs");
+const REGISTRY: &str = "src/days/mod.rs";
+
+pub fn module_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("src/days/day{}.rs", day))
+}
+
+pub fn bin_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("src/bin/day{}.rs", day))
+}
+
+/// The solution module of `day`, generated from `template.rs`.
+pub fn module(day: u8) -> String {
+    TEMPLATE.replace("__DAY__", &day.to_string())
+}
+
+fn bin(day: u8) -> String {
+    format!(
+        "fn main() -> anyhow::Result<()> {{\n    aoc2022::runner::main({})\n}}\n",
+        day
+    )
+}
+
+/// Adds `day` to the `mod` declarations and to [`crate::days::ALL`], keeping both ordered.
+fn register(registry: &str, day: u8) -> Result<String> {
+    let module = format!("pub mod day{};", day);
+    let entry = format!("    &day{0}::Day{0},", day);
+
+    ensure!(
+        !registry.lines().any(|line| line == module),
+        "Day {} is already registered in `{}`",
+        day,
+        REGISTRY
+    );
+
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+
+    // rustfmt orders modules by name as strings, so `day10` comes before `day2`.
+    let name = format!("day{}", day);
+    let module_name = |line: &str| -> Option<String> {
+        line.strip_prefix("pub mod ")?
+            .strip_suffix(';')
+            .map(String::from)
+    };
+    let at = lines
+        .iter()
+        .position(|line| module_name(line).is_some_and(|m| m > name))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| module_name(line).is_some())
+                .map(|i| i + 1)
+        })
+        .context("No `pub mod dayN;` declarations found")?;
+    lines.insert(at, module);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static ALL"))
+        .context("No `ALL` registry found")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "];")
+            .context("Unterminated `ALL` registry")?;
+
+    let entry_day = |line: &str| -> Option<u8> {
+        line.trim()
+            .strip_prefix("&day")?
+            .split("::")
+            .next()?
+            .parse()
+            .ok()
+    };
+    let at = (start + 1..end)
+        .find(|&i| entry_day(&lines[i]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the solution module, binary and (empty) inputs of `day` under `root`, and registers it.
+///
+/// Returns the files which were created. Existing inputs are kept, but an existing solution is
+/// never replaced.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    ensure!((1..=25).contains(&day), "There is no day {}", day);
+
+    for path in [module_path(day), bin_path(day)] {
+        if root.join(&path).exists() {
+            bail!("Day {} already exists: `{}`", day, path.display());
+        }
+    }
+
+    let registry_path = root.join(REGISTRY);
+    let registry = std::fs::read_to_string(&registry_path)
+        .context(format!("Failed to read `{}`", registry_path.display()))?;
+    let registry = register(&registry, day)?;
+
+    let inputs = [
+        crate::runner::input_path(day),
+        crate::runner::example_path(day),
+    ];
+    let mut files = vec![(module_path(day), module(day)), (bin_path(day), bin(day))];
+    files.extend(
+        inputs
+            .into_iter()
+            .filter(|path| !root.join(path).exists())
+            .map(|path| (path, String::new())),
+    );
+
+    for (path, contents) in &files {
+        let path = root.join(path);
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, contents).context(format!("Failed to write `{}`", path.display()))?;
+    }
+
+    std::fs::write(&registry_path, registry)
+        .context(format!("Failed to write `{}`", registry_path.display()))?;
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[test]
+fn test_register() {
+    let registry = "use crate::solution::DynSolution;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+
+pub static ALL: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day10::Day10,
+];
+";
+
+    assert_eq!(
+        register(registry, 3).unwrap(),
+        "use crate::solution::DynSolution;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+
+pub static ALL: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day10::Day10,
+];
+"
+    );
+
+    let registry = register(registry, 11).unwrap();
+    assert!(registry.contains("pub mod day10;\npub mod day11;\npub mod day2;"));
+    assert!(registry.contains("    &day10::Day10,\n    &day11::Day11,\n];"));
+
+    assert!(register(&registry, 2).is_err());
+}
+
+#[test]
+fn test_new_day() {
+    let root = std::env::temp_dir().join(format!("aoc2022-scaffold-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("src/days")).unwrap();
+    std::fs::write(root.join(REGISTRY), include_str!("days/mod.rs")).unwrap();
+
+    let created = new_day(&root, 12).unwrap();
+    assert_eq!(created.len(), 4);
+    assert_eq!(
+        std::fs::read_to_string(root.join("input/day12")).unwrap(),
+        ""
+    );
+
+    let module = std::fs::read_to_string(root.join("src/days/day12.rs")).unwrap();
+    assert!(module.contains("pub struct Day12;"));
+    assert!(module.contains("const DAY: u8 = 12;"));
+    assert!(module.contains("\"../../input/day12_test\""));
+    assert_eq!(
+        std::fs::read_to_string(root.join("input/day12_test")).unwrap(),
+        ""
+    );
+
+    let registry = std::fs::read_to_string(root.join(REGISTRY)).unwrap();
+    assert!(registry.contains("pub mod day11;\npub mod day12;\n"));
+    assert!(registry.contains("    &day11::Day11,\n    &day12::Day12,\n];"));
+
+    // Inputs are kept, but an existing day is never replaced.
+    std::fs::write(root.join("input/day13"), "1\n").unwrap();
+    assert_eq!(new_day(&root, 13).unwrap().len(), 3);
+    assert_eq!(
+        std::fs::read_to_string(root.join("input/day13")).unwrap(),
+        "1\n"
+    );
+
+    let e = new_day(&root, 12).unwrap_err();
+    assert_eq!(e.to_string(), "Day 12 already exists: `src/days/day12.rs`");
+    assert!(new_day(&root, 26).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
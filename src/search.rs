@@ -0,0 +1,415 @@
+//! Shortest paths over implicit graphs and grids.
+//!
+//! Graphs are given as a successor function, so nodes can be anything hashable: grid points,
+//! `(point, time)` pairs, bitsets of opened valves, ...
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::grid::{Cells, Point, ORTHOGONAL};
+
+/// The outcome of a search: the distance to every settled node, and how it was reached.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// The first goal reached, if any.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of reaching the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.cost_to(self.goal.as_ref()?)
+    }
+
+    pub fn cost_to(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// The shortest distance from the nearest start to every settled node.
+    ///
+    /// When the search stopped at a goal, only the nodes settled before it are included.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// The path from a start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// The path from a start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+
+        while let Some(parent) = self.parents.get(path.last().expect("path is never empty")) {
+            path.push(parent.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from all of `starts` at once, stopping at the first node where `is_goal`
+/// holds. Every edge costs 1.
+///
+/// Use `|_| false` as the goal to compute a full distance map.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        let distance = search.distances[&node];
+
+        for next in successors(&node) {
+            if search.distances.contains_key(&next) {
+                continue;
+            }
+
+            search.distances.insert(next.clone(), distance + 1);
+            search.parents.insert(next.clone(), node.clone());
+            queue.push_back(next);
+        }
+    }
+
+    search
+}
+
+/// A frontier entry, ordered so that [`BinaryHeap`] pops the lowest priority first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+    parent: Option<N>,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// A* search from all of `starts` at once, stopping at the first node where `is_goal` holds.
+///
+/// Nodes are settled the first time they are popped, so `heuristic` must be consistent: it never
+/// overestimates the remaining cost to a goal, and never drops by more than the cost of a step,
+/// i.e. `heuristic(a) <= step + heuristic(b)` for every step from `a` to `b`. `C::default()` is
+/// the zero cost.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut best = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+
+    for start in starts {
+        best.insert(start.clone(), C::default());
+        frontier.push(Entry {
+            priority: heuristic(&start),
+            cost: C::default(),
+            node: start,
+            parent: None,
+        });
+    }
+
+    while let Some(Entry {
+        cost, node, parent, ..
+    }) = frontier.pop()
+    {
+        if search.distances.contains_key(&node) {
+            continue;
+        }
+
+        search.distances.insert(node.clone(), cost);
+        if let Some(parent) = parent {
+            search.parents.insert(node.clone(), parent);
+        }
+
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+
+            if search.distances.contains_key(&next) || best.get(&next).is_some_and(|&b| b <= cost) {
+                continue;
+            }
+
+            best.insert(next.clone(), cost);
+            frontier.push(Entry {
+                priority: cost + heuristic(&next),
+                cost,
+                node: next,
+                parent: Some(node.clone()),
+            });
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm from all of `starts` at once, stopping at the first node where `is_goal`
+/// holds.
+///
+/// Use `|_| false` as the goal to compute a full distance map.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// The orthogonal neighbours of `p` in `grid` which can be entered, with the cost of the step.
+fn grid_steps<'a, T: 'a, C, G: Cells<T>>(
+    grid: &'a G,
+    p: Point,
+    cost: &'a impl Fn(&T, &T) -> Option<C>,
+) -> impl Iterator<Item = (Point, C)> + 'a {
+    let from = grid.cell(p);
+
+    ORTHOGONAL.iter().filter_map(move |d| {
+        let next = p + *d;
+        let step = cost(from?, grid.cell(next)?)?;
+
+        Some((next, step))
+    })
+}
+
+/// [`bfs`] over the orthogonal neighbours of a grid, where `can_move(from, to)` tells whether a
+/// step between two cells is allowed.
+pub fn grid_bfs<T, G: Cells<T>>(
+    grid: &G,
+    starts: impl IntoIterator<Item = Point>,
+    can_move: impl Fn(&T, &T) -> bool,
+    is_goal: impl FnMut(&Point) -> bool,
+) -> Search<Point, usize> {
+    let cost = |from: &T, to: &T| can_move(from, to).then_some(());
+
+    bfs(
+        starts,
+        |&p| grid_steps(grid, p, &cost).map(|(next, _)| next),
+        is_goal,
+    )
+}
+
+/// [`dijkstra`] over the orthogonal neighbours of a grid, where `cost(from, to)` is the cost of a
+/// step between two cells, or `None` if it is not allowed.
+pub fn grid_dijkstra<T, C, G: Cells<T>>(
+    grid: &G,
+    starts: impl IntoIterator<Item = Point>,
+    cost: impl Fn(&T, &T) -> Option<C>,
+    is_goal: impl FnMut(&Point) -> bool,
+) -> Search<Point, C>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    dijkstra(starts, |&p| grid_steps(grid, p, &cost), is_goal)
+}
+
+/// [`astar`] over the orthogonal neighbours of a grid towards `goal`, see [`grid_dijkstra`].
+///
+/// The Manhattan distance to `goal` times the cheapest possible step is a consistent heuristic.
+pub fn grid_astar<T, C, G: Cells<T>>(
+    grid: &G,
+    starts: impl IntoIterator<Item = Point>,
+    cost: impl Fn(&T, &T) -> Option<C>,
+    goal: Point,
+    heuristic: impl FnMut(&Point) -> C,
+) -> Search<Point, C>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    astar(
+        starts,
+        |&p| grid_steps(grid, p, &cost),
+        heuristic,
+        |&p| p == goal,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, InfiniteGrid};
+
+    const HILL: &str = "
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+    fn hill() -> (Grid<u8>, Point, Point) {
+        let grid = Grid::parse(HILL, |c| Ok::<_, &str>(c as u8)).unwrap();
+        let find = |c| grid.points().find(|&p| grid[p] == c).unwrap();
+        let (start, end) = (find(b'S'), find(b'E'));
+
+        let heights = grid.map(|&c| match c {
+            b'S' => b'a',
+            b'E' => b'z',
+            c => c,
+        });
+
+        (heights, start, end)
+    }
+
+    #[test]
+    fn test_grid_bfs() {
+        let (grid, start, end) = hill();
+        let climb = |&from: &u8, &to: &u8| to <= from + 1;
+
+        let search = grid_bfs(&grid, [start], climb, |&p| p == end);
+        assert_eq!(search.cost(), Some(31));
+
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 32);
+        assert_eq!((path[0], *path.last().unwrap()), (start, end));
+        assert!(path.windows(2).all(|w| {
+            let (a, b) = (w[0], w[1]);
+            (a.x - b.x).abs() + (a.y - b.y).abs() == 1 && climb(&grid[a], &grid[b])
+        }));
+
+        // Multi-source: the shortest hike from any lowest square.
+        let lowest = grid.points().filter(|&p| grid[p] == b'a');
+        let search = grid_bfs(&grid, lowest, climb, |&p| p == end);
+        assert_eq!(search.cost(), Some(29));
+        assert_eq!(grid[search.path().unwrap()[0]], b'a');
+
+        // A full distance map, and a goal which can't be reached.
+        let search = grid_bfs(&grid, [end], |&from, &to| from <= to + 1, |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.distances().len(), 40);
+        assert_eq!(search.cost_to(&start), Some(31));
+        assert_eq!(search.path_to(&(-1, 0).into()), None);
+    }
+
+    #[test]
+    fn test_grid_dijkstra_astar() {
+        let grid = Grid::parse(
+            "
+131
+191
+111
+",
+            |c| c.to_digit(10).ok_or("a digit"),
+        )
+        .unwrap();
+        let enter = |_: &u32, &to: &u32| Some(to);
+        let (start, goal) = (Point::from((1, 0)), Point::from((1, 2)));
+
+        let search = grid_dijkstra(&grid, [start], enter, |&p| p == goal);
+        assert_eq!(search.cost(), Some(4));
+        assert_eq!(search.path().unwrap().len(), 5);
+
+        let search = grid_astar(&grid, [start], enter, goal, |p| p.manhattan(goal) as u32);
+        assert_eq!(search.cost(), Some(4));
+
+        let mut walls = InfiniteGrid::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                walls.entry((x, y).into()).or_insert((x, y) != (1, 1));
+            }
+        }
+        let open = |_: &bool, &to: &bool| to.then_some(1);
+        let search = grid_dijkstra(&walls, [(0, 1).into()], open, |&p| p == (2, 1).into());
+        assert_eq!(search.cost(), Some(4));
+    }
+
+    #[test]
+    fn test_implicit_graph() {
+        // Reach 100 from 1, by adding one or doubling.
+        let successors = |&n: &u32| [n + 1, n * 2].into_iter().filter(|&n| n <= 100);
+
+        let search = bfs([1], successors, |&n| n == 100);
+        assert_eq!(search.cost(), Some(8));
+        assert_eq!(search.path().unwrap(), [1, 2, 3, 6, 12, 24, 25, 50, 100]);
+
+        // Doubling is expensive: 1 -> 12 -> 24 -> 25 -> 50 -> 100.
+        let weighted = |&n: &u32| {
+            [(n + 1, 1), (n * 2, 10)]
+                .into_iter()
+                .filter(|&(n, _)| n <= 100)
+        };
+        let search = dijkstra([1], weighted, |&n| n == 100);
+        assert_eq!(search.cost(), Some(42));
+
+        let search = astar([1], weighted, |_| 0, |&n| n == 100);
+        assert_eq!(search.cost(), Some(42));
+
+        let search = dijkstra([200], weighted, |&n| n == 100);
+        assert_eq!((search.goal(), search.cost()), (None, None));
+    }
+}
@@ -0,0 +1,85 @@
+use std::any::Any;
+
+use anyhow::Result;
+
+use crate::answer::Answer;
+
+/// A single day of the calendar.
+///
+/// Implementors are unit structs living in [`crate::days`], registered in [`crate::days::ALL`].
+pub trait Solution {
+    const DAY: u8;
+
+    type Input: 'static;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+
+    fn parse_input(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+
+    /// Alternative implementations of the parts, benchmarked next to them by `aoc bench`.
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![]
+    }
+}
+
+/// An alternative implementation of a part, e.g. on another data structure.
+pub struct Variant<I> {
+    pub name: &'static str,
+    pub run: fn(&I) -> Result<Answer>,
+}
+
+/// A [`Variant`] of a [`DynSolution`], taking the input returned by [`DynSolution::parse`].
+pub type DynVariant = (&'static str, Box<dyn Fn(&dyn Any) -> Result<Answer>>);
+
+/// Object safe version of [`Solution`], so days with different input types can share a registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer>;
+
+    fn part2(&self, input: &dyn Any) -> Result<Answer>;
+
+    fn variants(&self) -> Vec<DynVariant>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse_input(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer> {
+        S::part1(downcast::<S>(input)).map(Into::into)
+    }
+
+    fn part2(&self, input: &dyn Any) -> Result<Answer> {
+        S::part2(downcast::<S>(input)).map(Into::into)
+    }
+
+    fn variants(&self) -> Vec<DynVariant> {
+        S::variants()
+            .into_iter()
+            .map(|variant| -> DynVariant {
+                (
+                    variant.name,
+                    Box::new(move |input| (variant.run)(downcast::<S>(input))),
+                )
+            })
+            .collect()
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref()
+        .expect("input should be parsed by the same solution")
+}
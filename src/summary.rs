@@ -0,0 +1,197 @@
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+use crate::{answer::Answer, answers::KnownAnswers, runner::DayRun};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Matches the accepted answer.
+    Ok,
+    /// No accepted answer is recorded.
+    Unknown,
+    Changed,
+    Failed,
+}
+
+impl Status {
+    fn new(known: Option<&Answer>, answer: Option<&Answer>) -> Self {
+        match (known, answer) {
+            (_, None) => Status::Failed,
+            (None, Some(_)) => Status::Unknown,
+            (Some(known), Some(answer)) if known == answer => Status::Ok,
+            (Some(_), Some(_)) => Status::Changed,
+        }
+    }
+
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Changed | Status::Failed)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Ok => "ok",
+            Status::Unknown => "?",
+            Status::Changed => "CHANGED",
+            Status::Failed => "FAILED",
+        })
+    }
+}
+
+/// The runs of several days, checked against their accepted answers.
+pub struct Summary {
+    pub rows: Vec<(DayRun, KnownAnswers)>,
+    /// Wall clock time of running all days.
+    pub total: Duration,
+}
+
+impl Summary {
+    /// The status of both parts of every day, in order.
+    pub fn statuses(&self) -> impl Iterator<Item = (u8, u8, Status)> + '_ {
+        self.rows.iter().flat_map(|(run, known)| {
+            [(1, &known.part1), (2, &known.part2)]
+                .into_iter()
+                .map(move |(part, known)| {
+                    let answer = run
+                        .parts
+                        .as_ref()
+                        .ok()
+                        .and_then(|parts| parts[part as usize - 1].answer.as_ref().ok());
+
+                    (run.day, part, Status::new(known.as_ref(), answer))
+                })
+        })
+    }
+
+    pub fn failures(&self) -> usize {
+        self.statuses()
+            .filter(|(_, _, status)| status.is_failure())
+            .count()
+    }
+}
+
+/// A single line version of `answer`, cut to fit the table.
+fn cell(answer: &Answer) -> String {
+    const WIDTH: usize = 20;
+
+    if answer.is_multiline() {
+        return "(rendered)".to_string();
+    }
+
+    let answer = answer.to_string();
+
+    if answer.chars().count() > WIDTH {
+        answer.chars().take(WIDTH - 1).chain(['…']).collect()
+    } else {
+        answer
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3} {:>4}  {:20} {:>12} {:>12}  Status",
+            "Day", "Part", "Answer", "Parse", "Solve"
+        )?;
+
+        let mut errors = vec![];
+        let mut statuses = self.statuses();
+
+        for (run, _) in &self.rows {
+            for part in 1..=2 {
+                let (_, _, status) = statuses.next().expect("a status per part");
+                let parse = format!("{:.2?}", run.parse_took);
+
+                let (answer, solve) = match &run.parts {
+                    Ok(parts) => {
+                        let part = &parts[part - 1];
+
+                        match &part.answer {
+                            Ok(answer) => (cell(answer), format!("{:.2?}", part.took)),
+                            Err(e) => {
+                                errors.push(format!("Day {} part {}: {:#}", run.day, part.part, e));
+                                ("-".to_string(), format!("{:.2?}", part.took))
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        if part == 1 {
+                            errors.push(format!("Day {}: {:#}", run.day, e));
+                        }
+                        ("-".to_string(), "-".to_string())
+                    }
+                };
+
+                writeln!(
+                    f,
+                    "{:>3} {:>4}  {:20} {:>12} {:>12}  {}",
+                    run.day, part, answer, parse, solve, status
+                )?;
+            }
+        }
+
+        for error in errors {
+            writeln!(f, "{}", error)?;
+        }
+
+        write!(
+            f,
+            "Total: {:.2?} for {} day(s), {} failure(s)",
+            self.total,
+            self.rows.len(),
+            self.failures()
+        )
+    }
+}
+
+#[test]
+fn test_summary() {
+    use crate::{days::day1::Day1, runner::solve};
+
+    let known = KnownAnswers {
+        part1: Some(Answer::from(4)),
+        part2: Some(Answer::from(1)),
+    };
+
+    let summary = Summary {
+        rows: vec![
+            (solve(&Day1, "1\n2\n\n4"), known.clone()),
+            (solve(&Day1, "1\nx\n"), KnownAnswers::default()),
+        ],
+        total: Duration::from_millis(3),
+    };
+
+    assert_eq!(
+        summary.statuses().collect::<Vec<_>>(),
+        [
+            (1, 1, Status::Ok),
+            (1, 2, Status::Changed),
+            (1, 1, Status::Failed),
+            (1, 2, Status::Failed),
+        ]
+    );
+    assert_eq!(summary.failures(), 3);
+
+    let table = summary.to_string();
+    let lines = table.lines().collect::<Vec<_>>();
+
+    assert!(lines[1].starts_with("  1    1  4    "), "{}", lines[1]);
+    assert!(lines[1].ends_with("  ok"));
+    assert!(lines[2].ends_with("  CHANGED"));
+    assert!(lines[3].contains(" -  "));
+    // Parse errors come with an excerpt of the input.
+    assert!(lines[5].starts_with("Day 1: Failed to parse input: line 2, column 1"));
+    assert_eq!(
+        lines.last().unwrap(),
+        &"Total: 3.00ms for 2 day(s), 3 failure(s)"
+    );
+
+    assert_eq!(
+        cell(&Answer::from("x".repeat(30))),
+        format!("{}…", "x".repeat(19))
+    );
+}
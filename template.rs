@@ -1,38 +1,40 @@
-use std::io;
+use anyhow::{bail, Result};
 
-use anyhow::{Context, Result};
-use itertools::Itertools;
+use crate::{
+    parse::{int, Span},
+    solution::Solution,
+};
 
-type Input = ();
+type Input = Vec<i64>;
 
-fn parse_input(input: &str) -> Result<Input> {
-    todo!()
-}
-
-fn part1(input: &Input) -> Result<i32> {
-    todo!()
-}
+pub struct Day__DAY__;
 
-fn part2(input: &Input) -> Result<i32> {
-    todo!()
-}
+impl Solution for Day__DAY__ {
+    const DAY: u8 = __DAY__;
 
-#[test]
-fn test() {
-    todo!()
-}
+    type Input = Input;
+    type Output1 = i64;
+    type Output2 = i64;
 
-fn main() -> Result<()> {
-    let input = std::io::read_to_string(io::stdin()).context("Failed to read input.")?;
+    fn parse_input(input: &str) -> Result<Input> {
+        Ok(Span::new(input).parse_lines(int)?)
+    }
 
-    let (parsed, took) = aoc2022::timed(|| parse_input(&input).unwrap());
-    println!("Parsing input took: {}ms", took.as_millis());
+    fn part1(_input: &Input) -> Result<i64> {
+        bail!("Not solved yet")
+    }
 
-    // let (results, took) = aoc2022::timed(|| part1(&parsed).unwrap());
-    // println!("Part 1 answer: `{}`; took: {}ms", results, took.as_millis());
+    fn part2(_input: &Input) -> Result<i64> {
+        bail!("Not solved yet")
+    }
+}
 
-    // let (results, took) = aoc2022::timed(|| part2(&parsed).unwrap());
-    // println!("Part 2 answer: `{}`; took: {}ms", results, took.as_millis());
+#[test]
+#[ignore = "fill in the answers for the example"]
+fn test_day__DAY__() {
+    let input = include_str!("../../input/day__DAY___test");
+    let parsed = Day__DAY__::parse_input(input).unwrap();
 
-    Ok(())
+    assert_eq!(Day__DAY__::part1(&parsed).unwrap(), 0);
+    assert_eq!(Day__DAY__::part2(&parsed).unwrap(), 0);
 }